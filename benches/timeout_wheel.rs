@@ -0,0 +1,103 @@
+//! Compares per-stream timers against a shared `TimeoutWheel`.
+//!
+//! Runs 10k streams at once, each yielding items with a pending poll in
+//! between, so every item moves the deadline of its stream. Run with:
+//!
+//! ```sh
+//! cargo bench --bench timeout_wheel
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use futures_lite::future;
+use futures_lite::stream::Stream;
+use futures_time::prelude::*;
+use futures_time::stream::TimeoutWheel;
+use futures_time::time::Duration;
+
+const STREAMS: usize = 10_000;
+const ITEMS: usize = 100;
+
+/// A stream which is pending between every two items.
+struct Bursty {
+    remaining: usize,
+    ready: bool,
+}
+
+impl Bursty {
+    fn new() -> Self {
+        Self {
+            remaining: ITEMS,
+            ready: false,
+        }
+    }
+}
+
+impl Stream for Bursty {
+    type Item = usize;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.remaining == 0 {
+            return Poll::Ready(None);
+        }
+        self.ready = !self.ready;
+        match self.ready {
+            true => {
+                self.remaining -= 1;
+                Poll::Ready(Some(self.remaining))
+            }
+            false => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Poll all streams to completion, returning the number of items yielded.
+fn drive<S: Stream + Unpin>(mut streams: Vec<S>) -> usize {
+    let mut count = 0;
+    async_io::block_on(future::poll_fn(|cx| {
+        streams.retain_mut(|stream| loop {
+            match Pin::new(&mut *stream).poll_next(cx) {
+                Poll::Ready(Some(_)) => count += 1,
+                Poll::Ready(None) => return false,
+                Poll::Pending => return true,
+            }
+        });
+        match streams.is_empty() {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }
+    }));
+    count
+}
+
+fn main() {
+    let dur = Duration::from_secs(10);
+
+    let start = Instant::now();
+    let streams = (0..STREAMS)
+        .map(|_| Bursty::new().timeout(dur))
+        .collect::<Vec<_>>();
+    let count = drive(streams);
+    println!(
+        "per-stream timers: {} items in {:?}",
+        count,
+        start.elapsed()
+    );
+
+    let start = Instant::now();
+    let wheel = TimeoutWheel::new(Duration::from_millis(10));
+    let streams = (0..STREAMS)
+        .map(|_| Bursty::new().timeout_on(&wheel, dur))
+        .collect::<Vec<_>>();
+    let count = drive(streams);
+    println!(
+        "shared wheel:      {} items in {:?}",
+        count,
+        start.elapsed()
+    );
+}
@@ -0,0 +1,162 @@
+//! An async multi-producer multi-consumer channel.
+//!
+//! This module also contains the types used to suspend and resume futures and
+//! streams through [`FutureExt::park`] and [`StreamExt::park`]. A [`parker`]
+//! creates an [`Unparker`] handle together with the receiver to pass to
+//! either method.
+//!
+//! [`FutureExt::park`]: crate::future::FutureExt::park
+//! [`StreamExt::park`]: crate::stream::StreamExt::park
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::Stream;
+
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use async_channel::*;
+
+/// Suspend or resume execution of a future or stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Parker {
+    /// Put the future into a suspended state.
+    Park,
+    /// Put the future into an active state.
+    Unpark,
+}
+
+/// Create a new handle to suspend and resume a future or stream.
+///
+/// The returned receiver can be passed to [`FutureExt::park`] or
+/// [`StreamExt::park`], and the [`Unparker`] is used to control the parked
+/// future or stream. Each message is received once, so every parked future or
+/// stream needs its own `parker`. The `Unparker` can be cloned to control it
+/// from multiple places.
+///
+/// [`FutureExt::park`]: crate::future::FutureExt::park
+/// [`StreamExt::park`]: crate::stream::StreamExt::park
+///
+/// # Example
+///
+/// ```
+/// use futures_time::prelude::*;
+/// use futures_time::channel;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let (unparker, receiver) = channel::parker();
+///         let fut = async { "meow" }.park(receiver);
+///         unparker.unpark();
+///         assert_eq!(fut.await, "meow");
+///     });
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn parker() -> (Unparker, Receiver<Parker>) {
+    let (sender, receiver) = unbounded();
+    (Unparker { sender }, receiver)
+}
+
+/// A handle to suspend and resume a future or stream.
+///
+/// This `struct` is created by the [`parker`] function. See its documentation
+/// for more.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Unparker {
+    sender: Sender<Parker>,
+}
+
+#[cfg(feature = "std")]
+impl Unparker {
+    /// Suspend the future or stream.
+    ///
+    /// This does not block: the message is picked up the next time the parked
+    /// future or stream is polled.
+    pub fn park(&self) {
+        let _ = self.sender.try_send(Parker::Park);
+    }
+
+    /// Resume the future or stream.
+    ///
+    /// This does not block: the parked future or stream is woken up, and
+    /// resumes the next time it is polled.
+    pub fn unpark(&self) {
+        let _ = self.sender.try_send(Parker::Unpark);
+    }
+
+    /// Resume the future or stream, and return whether the message was
+    /// delivered.
+    ///
+    /// Like [`unpark`](Unparker::unpark) this does not block: the message is
+    /// sent without waiting for room in the channel, so it can be called from
+    /// synchronous code such as a `Drop` impl. It returns `false` if the
+    /// parked future or stream has been dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::channel;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let (unparker, receiver) = channel::parker();
+    ///         let fut = async { "meow" }.park(receiver);
+    ///         assert!(unparker.unpark_now());
+    ///         assert_eq!(fut.await, "meow");
+    ///         assert!(!unparker.unpark_now());
+    ///     });
+    /// }
+    /// ```
+    pub fn unpark_now(&self) -> bool {
+        self.sender.try_send(Parker::Unpark).is_ok()
+    }
+}
+
+/// The state of a parked future or stream, shared by `future::Park` and
+/// `stream::Park`.
+#[derive(Debug)]
+pub(crate) enum ParkState {
+    /// The future or stream may be polled.
+    Active,
+    /// The future or stream has been suspended, so we wait for a message from
+    /// the channel.
+    Suspended,
+    /// The channel has been dropped, no more need to check it!
+    NoChannel,
+}
+
+impl ParkState {
+    /// Apply all pending messages from the channel, and return whether the
+    /// future or stream may be polled.
+    ///
+    /// When this returns `Poll::Pending` the task will be woken by the next
+    /// message from the channel.
+    pub(crate) fn poll_active<I>(
+        &mut self,
+        mut channel: Pin<&mut I>,
+        cx: &mut Context<'_>,
+    ) -> Poll<()>
+    where
+        I: Stream<Item = Parker>,
+    {
+        loop {
+            if let ParkState::NoChannel = self {
+                return Poll::Ready(());
+            }
+            match channel.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Parker::Park)) => *self = ParkState::Suspended,
+                Poll::Ready(Some(Parker::Unpark)) => *self = ParkState::Active,
+                Poll::Ready(None) => *self = ParkState::NoChannel,
+                Poll::Pending => {
+                    return match self {
+                        ParkState::Suspended => Poll::Pending,
+                        _ => Poll::Ready(()),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+use std::ops::DerefMut;
+use std::task::{Context, Poll};
+use std::{future::Future, pin::Pin};
+
+use crate::time::{Duration, Instant};
+
+/// A future which resolves at a deadline that can be moved.
+///
+/// Where [`Timer`](crate::future::Timer) can only restart a future using the
+/// duration it was created with, this trait allows the deadline to be moved to
+/// an arbitrary point in time. This is useful for operations such as watchdogs,
+/// which get "kicked" forward by varying amounts.
+pub trait Deadline: Future {
+    /// Move the deadline of this future to `Instant::now() + dur`. If the
+    /// future has already resolved before, calling this method will allow it
+    /// to resolve again.
+    fn push_deadline(self: Pin<&mut Self>, dur: Duration);
+
+    /// Move the deadline of this future to the given instant. If the future has
+    /// already resolved before, calling this method will allow it to resolve
+    /// again.
+    fn set_deadline(self: Pin<&mut Self>, at: Instant);
+
+    /// Returns the time left until the deadline, or `None` if the deadline
+    /// has already passed.
+    ///
+    /// This can be used to share a single budget between nested operations,
+    /// by passing what is left of an outer deadline down to an inner timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::future::Deadline;
+    /// use futures_time::task;
+    /// use futures_time::time::Duration;
+    ///
+    /// let budget = task::sleep(Duration::from_secs(10));
+    /// let remaining = budget.remaining().unwrap();
+    /// assert!(remaining <= Duration::from_secs(10));
+    /// assert!(remaining > Duration::from_secs(9));
+    /// ```
+    fn remaining(&self) -> Option<Duration>;
+
+    /// Returns the instant at which this future resolves.
+    ///
+    /// This reflects any changes made through [`push_deadline`] and
+    /// [`set_deadline`], as well as through [`Timer::reset_timer`] for types
+    /// which implement it.
+    ///
+    /// [`push_deadline`]: Deadline::push_deadline
+    /// [`set_deadline`]: Deadline::set_deadline
+    /// [`Timer::reset_timer`]: crate::future::Timer::reset_timer
+    fn deadline(&self) -> Instant;
+}
+
+impl<D: ?Sized + Deadline + Unpin> Deadline for &mut D {
+    fn push_deadline(mut self: Pin<&mut Self>, dur: Duration) {
+        Pin::new(&mut **self).push_deadline(dur)
+    }
+
+    fn set_deadline(mut self: Pin<&mut Self>, at: Instant) {
+        Pin::new(&mut **self).set_deadline(at)
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        (**self).remaining()
+    }
+
+    fn deadline(&self) -> Instant {
+        (**self).deadline()
+    }
+}
+
+impl<P> Deadline for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: Deadline,
+{
+    fn push_deadline(self: Pin<&mut Self>, dur: Duration) {
+        self.get_mut().as_mut().push_deadline(dur)
+    }
+
+    fn set_deadline(self: Pin<&mut Self>, at: Instant) {
+        self.get_mut().as_mut().set_deadline(at)
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        (**self).remaining()
+    }
+
+    fn deadline(&self) -> Instant {
+        (**self).deadline()
+    }
+}
+
+/// A dyn-compatible deadline, which erases the output type of the future.
+///
+/// [`Deadline`] is generic over the output of the future, so differently-typed
+/// deadlines can't be stored side by side. This trait is implemented for every
+/// [`Deadline`] with a `Sized` type, and for
+/// [`SignalReceiver`](crate::future::SignalReceiver), so they can all be stored
+/// as a [`BoxedDeadline`].
+pub trait DynDeadline {
+    /// Attempt to resolve the deadline, registering the current task for
+    /// wakeup if it has not passed yet.
+    fn poll_deadline(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>;
+
+    /// Move the deadline to the given instant. If the deadline has already
+    /// passed before, calling this method will allow it to resolve again.
+    ///
+    /// Deadlines which can't be moved, such as signals, ignore this.
+    fn reset_deadline(self: Pin<&mut Self>, at: Instant);
+}
+
+impl<D: Deadline> DynDeadline for D {
+    fn poll_deadline(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.poll(cx).map(|_| ())
+    }
+
+    fn reset_deadline(self: Pin<&mut Self>, at: Instant) {
+        self.set_deadline(at)
+    }
+}
+
+/// A boxed [`DynDeadline`], which can be passed to methods such as
+/// [`FutureExt::timeout`](crate::future::FutureExt::timeout).
+///
+/// # Example
+///
+/// ```
+/// use futures_time::prelude::*;
+/// use futures_time::future::{self, BoxedDeadline};
+/// use futures_time::task;
+/// use futures_time::time::{Duration, Instant};
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let (_sender, receiver) = future::signal();
+///         let mut deadlines = vec![
+///             BoxedDeadline::new(task::sleep(Duration::from_millis(10))),
+///             BoxedDeadline::new(task::sleep_until(Instant::now() + Duration::from_millis(10))),
+///             BoxedDeadline::new(receiver),
+///         ];
+///
+///         for deadline in &mut deadlines {
+///             let res = async { "meow" }.timeout(deadline).await;
+///             assert_eq!(res.unwrap(), "meow");
+///         }
+///     });
+/// }
+/// ```
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct BoxedDeadline {
+    inner: Pin<Box<dyn DynDeadline>>,
+}
+
+impl BoxedDeadline {
+    /// Box a deadline.
+    pub fn new<D: DynDeadline + 'static>(deadline: D) -> Self {
+        Self {
+            inner: Box::pin(deadline),
+        }
+    }
+
+    /// Move the deadline to the given instant. If the deadline has already
+    /// passed before, calling this method will allow it to resolve again.
+    ///
+    /// Deadlines which can't be moved, such as signals, ignore this.
+    pub fn reset_deadline(&mut self, at: Instant) {
+        self.inner.as_mut().reset_deadline(at)
+    }
+}
+
+impl fmt::Debug for BoxedDeadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxedDeadline").finish()
+    }
+}
+
+impl Future for BoxedDeadline {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.as_mut().poll_deadline(cx)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use super::BoxedDeadline;
+    use crate::future::signal;
+    use crate::prelude::*;
+    use crate::task::{sleep, sleep_until};
+    use crate::time::{Duration, Instant};
+    use futures_lite::future;
+
+    #[test]
+    fn heterogeneous_deadlines() {
+        crate::utils::block_on(async {
+            let (sender, receiver) = signal();
+            let mut deadlines = vec![
+                BoxedDeadline::new(sleep(Duration::from_millis(10))),
+                BoxedDeadline::new(sleep_until(Instant::now() + Duration::from_millis(10))),
+                BoxedDeadline::new(receiver),
+            ];
+            sender.notify();
+
+            for deadline in &mut deadlines {
+                let res = future::pending::<()>().timeout(deadline).await;
+                assert!(res.is_err());
+            }
+        })
+    }
+
+    #[test]
+    fn reset_deadline() {
+        crate::utils::block_on(async {
+            let mut deadline = BoxedDeadline::new(sleep(Duration::from_millis(10)));
+            (&mut deadline).await;
+
+            deadline.reset_deadline(Instant::now() + Duration::from_secs(10));
+            assert!(future::poll_once(&mut deadline).await.is_none());
+
+            deadline.reset_deadline(Instant::now());
+            (&mut deadline).await;
+        })
+    }
+}
@@ -1,10 +1,13 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::future::FusedFuture;
 use futures_core::ready;
-use std::future::Future;
-use std::pin::Pin;
-use std::task::{Context, Poll};
 
 use pin_project_lite::pin_project;
 
+use super::Timer;
+
 pin_project! {
     /// Suspends a future until the specified deadline.
     ///
@@ -20,6 +23,7 @@ pin_project! {
         #[pin]
         deadline: D,
         state: State,
+        start: Option<fn(Pin<&mut D>)>,
     }
 }
 
@@ -37,15 +41,54 @@ impl<F, D> Delay<F, D> {
             future,
             deadline,
             state: State::Started,
+            start: None,
         }
     }
 }
 
+impl<F, D: Timer> Delay<F, D> {
+    /// Start the delay when the future is first polled, rather than when it
+    /// was created.
+    ///
+    /// By default a relative delay counts from the moment [`delay`] is called.
+    /// With this option the timer is reset on the first poll, so the inner
+    /// future is always held back for the full duration after that.
+    ///
+    /// [`delay`]: crate::future::FutureExt::delay
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::task;
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let delay = Duration::from_millis(50);
+    ///         let fut = async { "meow" }.delay(delay).lazy_start();
+    ///         task::sleep(delay).await;
+    ///
+    ///         let start = Instant::now();
+    ///         assert_eq!(fut.await, "meow");
+    ///         assert!(start.elapsed() >= delay);
+    ///     });
+    /// }
+    /// ```
+    pub fn lazy_start(mut self) -> Self {
+        self.start = Some(|deadline| deadline.reset_timer());
+        self
+    }
+}
+
 impl<F: Future, D: Future> Future for Delay<F, D> {
     type Output = F::Output;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut this = self.project();
+        if let Some(start) = this.start.take() {
+            start(this.deadline.as_mut());
+        }
         loop {
             match this.state {
                 State::Started => {
@@ -57,8 +100,78 @@ impl<F: Future, D: Future> Future for Delay<F, D> {
                     *this.state = State::Completed;
                     return Poll::Ready(value);
                 }
-                State::Completed => panic!("future polled after completing"),
+                // Stay pending once completed, so this can be polled again.
+                State::Completed => return Poll::Pending,
             }
         }
     }
 }
+
+impl<F: Future, D: Future> FusedFuture for Delay<F, D> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Completed)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::{Duration, Instant};
+    use futures_core::future::FusedFuture;
+    use futures_lite::future;
+
+    #[test]
+    fn is_terminated() {
+        crate::utils::block_on(async {
+            let mut fut = future::ready("meow").delay(Duration::from_millis(10));
+            assert!(!fut.is_terminated());
+            assert_eq!((&mut fut).await, "meow");
+            assert!(fut.is_terminated());
+        })
+    }
+
+    #[test]
+    fn duration_and_instant() {
+        crate::utils::block_on(async {
+            // A duration is relative to when `delay` is called.
+            let start = Instant::now();
+            future::ready(()).delay(Duration::from_millis(20)).await;
+            assert!(start.elapsed() >= Duration::from_millis(20));
+
+            // An instant is absolute.
+            let deadline = Instant::now() + Duration::from_millis(20);
+            future::ready(()).delay(deadline).await;
+            assert!(Instant::now() >= deadline);
+
+            // An instant in the past does not delay.
+            let mut fut = future::ready(()).delay(start);
+            assert!(future::poll_once(&mut fut).await.is_some());
+        })
+    }
+
+    #[test]
+    fn lazy_start() {
+        future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let delay = Duration::from_millis(10);
+            let sleep = crate::task::Sleep::with_clock(delay, clock.clone());
+            let mut fut = future::ready("meow").delay(sleep).lazy_start();
+
+            // Time spent before the first poll is not counted.
+            clock.advance(Duration::from_millis(20));
+            assert!(future::poll_once(&mut fut).await.is_none());
+            clock.advance(delay);
+            assert_eq!(future::poll_once(&mut fut).await, Some("meow"));
+        })
+    }
+
+    #[test]
+    fn poll_after_completion() {
+        crate::utils::block_on(async {
+            let mut fut = future::ready("meow").delay(Duration::from_millis(10));
+            assert_eq!((&mut fut).await, "meow");
+            assert!(future::poll_once(&mut fut).await.is_none());
+            assert!(fut.is_terminated());
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use super::IntoFuture;
+
+/// Create a future once the given deadline has passed, and then wait for it.
+///
+/// `make` is only called once `deadline` has passed, so the future it returns
+/// is not created before then. This differs from
+/// [`FutureExt::delay`](crate::future::FutureExt::delay), which takes a future
+/// that has already been created, and merely postpones polling it. Any work
+/// which happens when that future is created, such as acquiring a resource,
+/// happens before the delay. Use this function to postpone that work until
+/// after the delay as well.
+///
+/// # Example
+///
+/// ```
+/// use futures_time::future;
+/// use futures_time::time::{Duration, Instant};
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let now = Instant::now();
+///         let delay = Duration::from_millis(100);
+///         let started = future::delay_lazy(delay, || {
+///             let started = Instant::now(); // runs after the delay
+///             async move { started }
+///         })
+///         .await;
+///         assert!(started - now >= delay);
+///     });
+/// }
+/// ```
+pub fn delay_lazy<D, F, Fut>(deadline: D, make: F) -> DelayLazy<D::IntoFuture, F, Fut>
+where
+    D: IntoFuture,
+    F: FnOnce() -> Fut,
+    Fut: Future,
+{
+    DelayLazy {
+        deadline: deadline.into_future(),
+        make: Some(make),
+        future: None,
+        completed: false,
+    }
+}
+
+pin_project! {
+    /// Creates a future once a deadline has passed, and then waits for it.
+    ///
+    /// This `struct` is created by the [`delay_lazy`] function. See its
+    /// documentation for more.
+    ///
+    /// [`delay_lazy`]: crate::future::delay_lazy
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct DelayLazy<D, F, Fut> {
+        #[pin]
+        deadline: D,
+        make: Option<F>,
+        #[pin]
+        future: Option<Fut>,
+        completed: bool,
+    }
+}
+
+impl<D, F, Fut> Future for DelayLazy<D, F, Fut>
+where
+    D: Future,
+    F: FnOnce() -> Fut,
+    Fut: Future,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if *this.completed {
+            return Poll::Pending;
+        }
+
+        if let Some(make) = this.make.take() {
+            if this.deadline.poll(cx).is_pending() {
+                *this.make = Some(make);
+                return Poll::Pending;
+            }
+            this.future.set(Some(make()));
+        }
+
+        let value = ready!(this.future.as_pin_mut().unwrap().poll(cx));
+        *this.completed = true;
+        Poll::Ready(value)
+    }
+}
+
+impl<D, F, Fut> FusedFuture for DelayLazy<D, F, Fut>
+where
+    D: Future,
+    F: FnOnce() -> Fut,
+    Fut: Future,
+{
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::future::delay_lazy;
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use core::cell::Cell;
+
+    #[test]
+    fn creates_future_after_deadline() {
+        crate::utils::block_on(async {
+            let created = Cell::new(false);
+            let mut fut = Box::pin(delay_lazy(Duration::from_millis(20), || {
+                created.set(true);
+                async { "meow" }
+            }));
+
+            let res = fut.as_mut().timeout(Duration::from_millis(5)).await;
+            assert!(res.is_err());
+            assert!(!created.get());
+
+            assert_eq!(fut.await, "meow");
+            assert!(created.get());
+        })
+    }
+}
@@ -0,0 +1,103 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use crate::time::{Duration, Instant};
+
+pin_project! {
+    /// Suspends a future until the specified deadline, and measures how long
+    /// the delay took.
+    ///
+    /// This `struct` is created by the [`delay_timed`] method on [`FutureExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`delay_timed`]: crate::future::FutureExt::delay_timed
+    /// [`FutureExt`]: crate::future::FutureExt
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct DelayTimed<F, D> {
+        #[pin]
+        future: F,
+        #[pin]
+        deadline: D,
+        start: Option<Instant>,
+        elapsed: Option<Duration>,
+        completed: bool,
+    }
+}
+
+impl<F, D> DelayTimed<F, D> {
+    pub(super) fn new(future: F, deadline: D) -> Self {
+        Self {
+            future,
+            deadline,
+            start: None,
+            elapsed: None,
+            completed: false,
+        }
+    }
+}
+
+impl<F: Future, D: Future> Future for DelayTimed<F, D> {
+    type Output = (F::Output, Duration);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if *this.completed {
+            return Poll::Pending;
+        }
+
+        if this.elapsed.is_none() {
+            let start = *this.start.get_or_insert_with(Instant::now);
+            ready!(this.deadline.poll(cx));
+            *this.elapsed = Some(start.elapsed());
+        }
+
+        let value = ready!(this.future.poll(cx));
+        *this.completed = true;
+        Poll::Ready((value, this.elapsed.unwrap()))
+    }
+}
+
+impl<F: Future, D: Future> FusedFuture for DelayTimed<F, D> {
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::task::sleep;
+    use crate::time::Duration;
+
+    #[test]
+    fn measures_only_the_delay() {
+        crate::utils::block_on(async {
+            let delay = Duration::from_millis(20);
+            let fut = async {
+                sleep(Duration::from_millis(100)).await;
+                "meow"
+            };
+            let (value, elapsed) = fut.delay_timed(delay).await;
+            assert_eq!(value, "meow");
+            assert!(elapsed >= delay);
+            assert!(elapsed < Duration::from_millis(100));
+        })
+    }
+
+    #[test]
+    fn past_deadline() {
+        crate::utils::block_on(async {
+            let deadline = crate::time::Instant::now();
+            let (value, elapsed) = async { 1 }.delay_timed(deadline).await;
+            assert_eq!(value, 1);
+            assert!(elapsed < Duration::from_millis(10));
+        })
+    }
+}
@@ -3,7 +3,13 @@ use core::future::Future;
 use crate::channel::Parker;
 use crate::stream::IntoStream;
 
-use super::{Delay, IntoFuture, Park, Timeout};
+use super::{Delay, IntoFuture, OnCancel, OrValue, Park, Timeout, TimeoutOrCancel, TryTimeout};
+#[cfg(feature = "std")]
+use super::{DelayTimed, Measure};
+cfg_rt! {
+    use super::{TimeoutInstrumented, TimeoutOutcome};
+    use crate::time::Duration;
+}
 
 /// Extend `Future` with time-based operations.
 pub trait FutureExt: Future {
@@ -17,6 +23,18 @@ pub trait FutureExt: Future {
     /// When a timeout is returned, the future will be dropped and destructors
     /// will be run.
     ///
+    /// The deadline starts counting down when this method is called. Use
+    /// [`Timeout::lazy_start`](crate::future::Timeout::lazy_start) to start it
+    /// when the future is first polled instead.
+    ///
+    /// The error is a [`TimeoutError`](crate::future::TimeoutError), which converts into an
+    /// [`io::Error`](std::io::Error) of kind `TimedOut` when the `std` feature is enabled.
+    ///
+    /// To share a single budget between several operations, pass the same
+    /// [`Sleep`](crate::task::Sleep) to each of them by `&mut` reference. Time
+    /// spent in one operation then counts against all the ones after it, and
+    /// once the sleep has completed every later timeout fails right away.
+    ///
     /// # Example
     ///
     /// ```
@@ -30,13 +48,18 @@ pub trait FutureExt: Future {
     ///             .delay(Duration::from_millis(100))  // longer delay
     ///             .timeout(Duration::from_millis(50)) // shorter timeout
     ///             .await;
-    ///         assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut); // error
+    ///         assert_eq!(io::Error::from(res.unwrap_err()).kind(), io::ErrorKind::TimedOut); // error
     ///
     ///         let res = async { "meow" }
     ///             .delay(Duration::from_millis(50))    // shorter delay
     ///             .timeout(Duration::from_millis(100)) // longer timeout
     ///             .await;
     ///         assert_eq!(res.unwrap(), "meow"); // success
+    ///
+    ///         let mut budget = futures_time::task::sleep(Duration::from_millis(100));
+    ///         let first = async { 1 }.timeout(&mut budget).await;
+    ///         let second = async { 2 }.timeout(&mut budget).await;
+    ///         assert_eq!((first.unwrap(), second.unwrap()), (1, 2));
     ///     });
     /// }
     /// ```
@@ -45,7 +68,128 @@ pub trait FutureExt: Future {
         Self: Sized,
         D: IntoFuture,
     {
-        Timeout::new(self, deadline.into_future())
+        let configured = deadline.configured_duration();
+        Timeout::new(self, deadline.into_future()).with_configured(configured)
+    }
+
+    cfg_rt! {
+        /// Return an error if a future does not complete within a given duration,
+        /// and report the outcome to an observer.
+        ///
+        /// This behaves like [`timeout`](FutureExt::timeout), but calls `observer`
+        /// exactly once when the future resolves: with the elapsed time if it
+        /// completed, or with the configured budget if it timed out. This is
+        /// useful to record metrics without wrapping every call site in a match.
+        /// Both the budget and the elapsed time are counted from the first poll.
+        /// The observer is not called if the future is dropped before resolving.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use futures_time::prelude::*;
+        /// use futures_time::future::TimeoutOutcome;
+        /// use futures_time::time::Duration;
+        ///
+        /// fn main() {
+        ///     async_io::block_on(async {
+        ///         let mut outcome = None;
+        ///         let res = async { "meow" }
+        ///             .delay(Duration::from_millis(100))
+        ///             .timeout_instrumented(Duration::from_millis(50), |o| outcome = Some(o))
+        ///             .await;
+        ///         assert!(res.is_err());
+        ///         let budget = Duration::from_millis(50);
+        ///         assert_eq!(outcome, Some(TimeoutOutcome::TimedOut { budget }));
+        ///     });
+        /// }
+        /// ```
+        fn timeout_instrumented<O>(self, dur: Duration, observer: O) -> TimeoutInstrumented<Self, O>
+        where
+            Self: Sized,
+            O: FnOnce(TimeoutOutcome),
+        {
+            TimeoutInstrumented::new(self, dur, observer)
+        }
+    }
+
+    /// Return an error along with the unfinished future if it does not
+    /// complete within a given time span.
+    ///
+    /// Unlike [`timeout`](FutureExt::timeout), the future is not dropped when
+    /// the deadline passes. It is handed back with the error instead, so the
+    /// progress it has made is kept and it can be awaited again, for example
+    /// with a fresh deadline. Because the future is moved out of the adapter,
+    /// it must be [`Unpin`]; use `Box::pin` to make any future `Unpin`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::task;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let mut fut = Box::pin(async {
+    ///             task::sleep(Duration::from_millis(50)).await;
+    ///             "meow"
+    ///         });
+    ///
+    ///         // Keep waiting in short steps, reporting progress in between.
+    ///         let mut attempts = 0;
+    ///         let res = loop {
+    ///             attempts += 1;
+    ///             match fut.try_timeout(Duration::from_millis(20)).await {
+    ///                 Ok(res) => break res,
+    ///                 Err((_, unfinished)) => fut = unfinished,
+    ///             }
+    ///         };
+    ///         assert_eq!(res, "meow");
+    ///         assert!(attempts > 1);
+    ///     });
+    /// }
+    /// ```
+    fn try_timeout<D>(self, deadline: D) -> TryTimeout<Self, D::IntoFuture>
+    where
+        Self: Sized + Unpin,
+        D: IntoFuture,
+    {
+        let configured = deadline.configured_duration();
+        TryTimeout::new(self, deadline.into_future()).with_configured(configured)
+    }
+
+    /// Resolve to a fallback value if a future does not complete within a
+    /// given time span.
+    ///
+    /// Unlike [`timeout`](FutureExt::timeout) the output type of the future is
+    /// unchanged, which is useful when the output already carries its own error
+    /// handling. Just like with `timeout`, any future can be used as a deadline.
+    ///
+    /// When the fallback is returned, the future will be dropped and destructors
+    /// will be run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let res = async { "meow" }
+    ///             .delay(Duration::from_millis(100))   // longer delay
+    ///             .or_value(Duration::from_millis(50), "purr") // shorter timeout
+    ///             .await;
+    ///         assert_eq!(res, "purr"); // fallback
+    ///     });
+    /// }
+    /// ```
+    fn or_value<D>(self, deadline: D, fallback: Self::Output) -> OrValue<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+    {
+        OrValue::new(self, deadline.into_future(), fallback)
     }
 
     /// Delay resolving the future until the given deadline.
@@ -55,6 +199,20 @@ pub trait FutureExt: Future {
     /// deadline too. When used in combination with a multi-consumer channel,
     /// this method can be used to synchronize the start of multiple futures and streams.
     ///
+    /// The type of the deadline decides how it is measured. A [`Duration`]
+    /// delays by that amount of time, counted from when this method is called.
+    /// An [`Instant`] delays until that point in time, so passing one means
+    /// "delay until". An instant which has already passed does not delay at
+    /// all.
+    ///
+    /// [`Duration`]: crate::time::Duration
+    /// [`Instant`]: crate::time::Instant
+    ///
+    /// The future has already been created when this method is called, so any
+    /// work done while creating it happens before the delay. Use
+    /// [`future::delay_lazy`](crate::future::delay_lazy) to create the future
+    /// once the deadline has passed instead.
+    ///
     /// # Example
     ///
     /// ```
@@ -66,7 +224,7 @@ pub trait FutureExt: Future {
     ///         let now = Instant::now();
     ///         let delay = Duration::from_millis(100);
     ///         let _ = async { "meow" }.delay(delay).await;
-    ///         assert!(now.elapsed() >= *delay);
+    ///         assert!(now.elapsed() >= delay);
     ///     });
     /// }
     /// ```
@@ -78,12 +236,99 @@ pub trait FutureExt: Future {
         Delay::new(self, deadline.into_future())
     }
 
+    /// Suspend the future until the deadline, and measure how long the delay
+    /// actually took.
+    ///
+    /// The returned future resolves to the output of the underlying future,
+    /// together with the time elapsed between the first time it was polled and
+    /// the deadline completing. Timers usually fire slightly late, so this can
+    /// be used to observe and compensate for that overshoot. Unlike
+    /// [`measure`](FutureExt::measure), the time the underlying future takes
+    /// after the delay is not counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let delay = Duration::from_millis(100);
+    ///         let (value, elapsed) = async { "meow" }.delay_timed(delay).await;
+    ///         assert_eq!(value, "meow");
+    ///         assert!(elapsed >= delay);
+    ///     });
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    fn delay_timed<D>(self, deadline: D) -> DelayTimed<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+    {
+        DelayTimed::new(self, deadline.into_future())
+    }
+
+    /// Measure how long it takes for a future to complete.
+    ///
+    /// The returned future resolves to the output of the underlying future,
+    /// together with the time elapsed between the first time it was polled and
+    /// its completion. Time spent before the first poll is not counted, so a
+    /// future which is created early but only awaited later is not charged for
+    /// the time it sat idle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let delay = Duration::from_millis(100);
+    ///         let (value, elapsed) = async { "meow" }.delay(delay).measure().await;
+    ///         assert_eq!(value, "meow");
+    ///         assert!(elapsed >= delay);
+    ///     });
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    fn measure(self) -> Measure<Self>
+    where
+        Self: Sized,
+    {
+        Measure::new(self)
+    }
+
     /// Suspend or resume execution of a future.
     ///
     /// When this method is called the execution of the future will be put into
     /// a suspended state until the channel returns `Parker::Unpark` or the
     /// channel's senders are dropped. The underlying future will not be polled
     /// while the it is paused.
+    ///
+    /// The easiest way to control the future is with a
+    /// [`parker`](crate::channel::parker), whose
+    /// [`Unparker`](crate::channel::Unparker) suspends and resumes it. Any other
+    /// stream of [`Parker`] values can be used as well, such as the receiving
+    /// end of a [`channel`](crate::channel).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::channel;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let (unparker, receiver) = channel::parker();
+    ///         let fut = async { "meow" }.park(receiver);
+    ///         unparker.unpark();
+    ///         assert_eq!(fut.await, "meow");
+    ///     });
+    /// }
+    /// ```
     fn park<I>(self, interval: I) -> Park<Self, I::IntoStream>
     where
         Self: Sized,
@@ -91,6 +336,94 @@ pub trait FutureExt: Future {
     {
         Park::new(self, interval.into_stream())
     }
+
+    /// Run a closure if a future is cancelled before it completes.
+    ///
+    /// This resolves to `Some` with the output of the future if it completes
+    /// first. If `cancel` completes first, `f` is called once and this
+    /// resolves to `None`, after which the future is not polled again. If
+    /// both are ready at the same time, the future is considered to have
+    /// completed and `f` is not called.
+    ///
+    /// Unlike a guard which runs on `Drop`, this tells cancellation apart from
+    /// completion, which is useful to log or count cancellations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::future;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let (sender, receiver) = future::signal();
+    ///         sender.notify();
+    ///         let mut cancelled = false;
+    ///         let res = async { "meow" }
+    ///             .delay(Duration::from_millis(100))
+    ///             .on_cancel(receiver, || cancelled = true)
+    ///             .await;
+    ///         assert_eq!(res, None);
+    ///         assert!(cancelled);
+    ///     });
+    /// }
+    /// ```
+    fn on_cancel<C, H>(self, cancel: C, f: H) -> OnCancel<Self, C::IntoFuture, H>
+    where
+        Self: Sized,
+        C: IntoFuture,
+        H: FnOnce(),
+    {
+        OnCancel::new(self, cancel.into_future(), f)
+    }
+
+    /// Return an error if a future does not complete within a given time
+    /// span, or if it is cancelled first.
+    ///
+    /// This resolves to `Ok` with the output of the future if it completes
+    /// first, to `Err(Interrupted::TimedOut)` if `deadline` completes first,
+    /// and to `Err(Interrupted::Cancelled)` if `cancel` completes first. Both
+    /// `deadline` and `cancel` can be any future, such as a [`Duration`] and
+    /// a [`signal`](crate::future::signal) receiver. If both complete at the
+    /// same time, cancellation takes precedence.
+    ///
+    /// When an error is returned, the future will be dropped and destructors
+    /// will be run.
+    ///
+    /// [`Duration`]: crate::time::Duration
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::future::{self, Interrupted};
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let (sender, receiver) = future::signal();
+    ///         sender.notify();
+    ///         let res = async { "meow" }
+    ///             .delay(Duration::from_millis(100))
+    ///             .timeout_or_cancel(Duration::from_millis(50), receiver)
+    ///             .await;
+    ///         assert_eq!(res, Err(Interrupted::Cancelled));
+    ///     });
+    /// }
+    /// ```
+    fn timeout_or_cancel<D, C>(
+        self,
+        deadline: D,
+        cancel: C,
+    ) -> TimeoutOrCancel<Self, D::IntoFuture, C::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+        C: IntoFuture,
+    {
+        TimeoutOrCancel::new(self, deadline.into_future(), cancel.into_future())
+    }
 }
 
 impl<T> FutureExt for T where T: Future {}
@@ -1,4 +1,6 @@
-use std::future::Future;
+use core::future::Future;
+
+use crate::time::Duration;
 
 /// Conversion into a `Future`.
 ///
@@ -60,6 +62,16 @@ pub trait IntoFuture {
     /// # }
     /// ```
     fn into_future(self) -> Self::IntoFuture;
+
+    /// Returns the duration the future waits for, if it is known before the
+    /// future is created.
+    ///
+    /// Timeouts read this from their deadline before converting it, and
+    /// include it in the [`TimeoutError`](crate::future::TimeoutError) they
+    /// return. The default implementation returns `None`.
+    fn configured_duration(&self) -> Option<Duration> {
+        None
+    }
 }
 
 impl<F: Future> IntoFuture for F {
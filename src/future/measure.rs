@@ -0,0 +1,86 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use pin_project_lite::pin_project;
+
+use crate::time::{Duration, Instant};
+
+pin_project! {
+    /// A future that measures how long the underlying future took to complete.
+    ///
+    /// This `struct` is created by the [`measure`] method on [`FutureExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`measure`]: crate::future::FutureExt::measure
+    /// [`FutureExt`]: crate::future::FutureExt
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct Measure<F> {
+        #[pin]
+        future: F,
+        start: Option<Instant>,
+        completed: bool,
+    }
+}
+
+impl<F> Measure<F> {
+    pub(super) fn new(future: F) -> Self {
+        Self {
+            future,
+            start: None,
+            completed: false,
+        }
+    }
+}
+
+impl<F: Future> Future for Measure<F> {
+    type Output = (F::Output, Duration);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if *this.completed {
+            return Poll::Pending;
+        }
+
+        let start = *this.start.get_or_insert_with(Instant::now);
+        match this.future.poll(cx) {
+            Poll::Ready(v) => {
+                *this.completed = true;
+                Poll::Ready((v, start.elapsed()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<F: Future> FusedFuture for Measure<F> {
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::task::sleep;
+    use crate::time::Duration;
+
+    #[test]
+    fn starts_at_first_poll() {
+        crate::utils::block_on(async {
+            let fut = async {
+                sleep(Duration::from_millis(10)).await;
+                "meow"
+            }
+            .measure();
+            sleep(Duration::from_millis(100)).await;
+            let (value, elapsed) = fut.await;
+            assert_eq!(value, "meow");
+            assert!(elapsed >= Duration::from_millis(10));
+            assert!(elapsed < Duration::from_millis(100));
+        })
+    }
+}
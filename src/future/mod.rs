@@ -39,16 +39,52 @@
 //! }
 //! ```
 
+#[cfg(feature = "std")]
+mod deadline;
 mod delay;
+mod delay_lazy;
+#[cfg(feature = "std")]
+mod delay_timed;
 mod future_ext;
 mod into_future;
+#[cfg(feature = "std")]
+mod measure;
+mod on_cancel;
+mod or_value;
 mod park;
 mod relative_future;
+#[cfg(feature = "std")]
+mod signal;
 mod timeout;
+mod timeout_error;
+mod timeout_or_cancel;
+mod try_timeout;
 
+cfg_rt! {
+    mod retry;
+    mod timeout_instrumented;
+
+    pub use retry::{retry, Retry};
+    pub use timeout_instrumented::{TimeoutInstrumented, TimeoutOutcome};
+}
+
+#[cfg(feature = "std")]
+pub use deadline::{BoxedDeadline, Deadline, DynDeadline};
 pub use delay::Delay;
+pub use delay_lazy::{delay_lazy, DelayLazy};
+#[cfg(feature = "std")]
+pub use delay_timed::DelayTimed;
 pub use future_ext::FutureExt;
 pub use into_future::IntoFuture;
+#[cfg(feature = "std")]
+pub use measure::Measure;
+pub use on_cancel::OnCancel;
+pub use or_value::OrValue;
 pub use park::Park;
 pub use relative_future::Timer;
+#[cfg(feature = "std")]
+pub use signal::{signal, SignalReceiver, SignalSender, SignalStream};
 pub use timeout::Timeout;
+pub use timeout_error::TimeoutError;
+pub use timeout_or_cancel::{Interrupted, TimeoutOrCancel};
+pub use try_timeout::TryTimeout;
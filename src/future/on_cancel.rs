@@ -0,0 +1,111 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A future which runs a closure when it is cancelled before completing.
+    ///
+    /// This `struct` is created by the [`on_cancel`] method on [`FutureExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`on_cancel`]: crate::future::FutureExt::on_cancel
+    /// [`FutureExt`]: crate::future::FutureExt
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct OnCancel<F, C, H> {
+        #[pin]
+        future: F,
+        #[pin]
+        cancel: C,
+        on_cancel: Option<H>,
+        completed: bool,
+    }
+}
+
+impl<F, C, H> OnCancel<F, C, H> {
+    pub(super) fn new(future: F, cancel: C, on_cancel: H) -> Self {
+        Self {
+            future,
+            cancel,
+            on_cancel: Some(on_cancel),
+            completed: false,
+        }
+    }
+}
+
+impl<F, C, H> Future for OnCancel<F, C, H>
+where
+    F: Future,
+    C: Future,
+    H: FnOnce(),
+{
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if *this.completed {
+            return Poll::Pending;
+        }
+
+        if let Poll::Ready(v) = this.future.poll(cx) {
+            *this.completed = true;
+            return Poll::Ready(Some(v));
+        }
+        match this.cancel.poll(cx) {
+            Poll::Ready(_) => {
+                *this.completed = true;
+                if let Some(on_cancel) = this.on_cancel.take() {
+                    on_cancel();
+                }
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<F, C, H> FusedFuture for OnCancel<F, C, H>
+where
+    F: Future,
+    C: Future,
+    H: FnOnce(),
+{
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use core::cell::Cell;
+
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::future;
+
+    #[test]
+    fn runs_only_on_cancel() {
+        crate::utils::block_on(async {
+            let cancelled = Cell::new(0);
+            let res = future::pending::<()>()
+                .on_cancel(Duration::from_millis(10), || {
+                    cancelled.set(cancelled.get() + 1)
+                })
+                .await;
+            assert_eq!(res, None);
+            assert_eq!(cancelled.get(), 1);
+
+            let res = future::ready("meow")
+                .on_cancel(Duration::from_millis(10), || {
+                    cancelled.set(cancelled.get() + 1)
+                })
+                .await;
+            assert_eq!(res, Some("meow"));
+            assert_eq!(cancelled.get(), 1);
+        })
+    }
+}
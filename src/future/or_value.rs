@@ -0,0 +1,82 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// A future that resolves to a fallback value if it does not complete
+    /// before a deadline.
+    ///
+    /// This `struct` is created by the [`or_value`] method on [`FutureExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`or_value`]: crate::future::FutureExt::or_value
+    /// [`FutureExt`]: crate::future::FutureExt
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct OrValue<F: Future, D> {
+        #[pin]
+        future: F,
+        #[pin]
+        deadline: D,
+        fallback: Option<F::Output>,
+    }
+}
+
+impl<F: Future, D> OrValue<F, D> {
+    pub(super) fn new(future: F, deadline: D, fallback: F::Output) -> Self {
+        Self {
+            future,
+            deadline,
+            fallback: Some(fallback),
+        }
+    }
+}
+
+impl<F: Future, D: Future> Future for OrValue<F, D> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.fallback.is_none() {
+            return Poll::Pending;
+        }
+
+        match this.future.poll(cx) {
+            Poll::Ready(v) => {
+                *this.fallback = None;
+                Poll::Ready(v)
+            }
+            Poll::Pending => match this.deadline.poll(cx) {
+                Poll::Ready(_) => Poll::Ready(this.fallback.take().unwrap()),
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+impl<F: Future, D: Future> FusedFuture for OrValue<F, D> {
+    fn is_terminated(&self) -> bool {
+        self.fallback.is_none()
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::future;
+
+    #[test]
+    fn completes_in_time() {
+        crate::utils::block_on(async {
+            let res = future::ready("meow")
+                .or_value(Duration::from_millis(10), "purr")
+                .await;
+            assert_eq!(res, "meow");
+        })
+    }
+}
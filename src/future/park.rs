@@ -1,9 +1,10 @@
-use std::future::Future;
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
 
-use crate::channel::Parker;
+use crate::channel::{ParkState, Parker};
 
+use futures_core::future::FusedFuture;
 use futures_core::{ready, Stream};
 use pin_project_lite::pin_project;
 
@@ -25,23 +26,11 @@ pin_project! {
         future: F,
         #[pin]
         interval: I,
-        state: State,
+        state: ParkState,
+        completed: bool,
     }
 }
 
-/// The internal state
-#[derive(Debug)]
-enum State {
-    /// Actively polling the future.
-    Active,
-    /// The future has been paused, so we wait for a signal from the channel.
-    Suspended,
-    /// The channel has been dropped, no more need to check it!
-    NoChannel,
-    /// The future has completed.
-    Completed,
-}
-
 impl<F, I> Park<F, I>
 where
     F: Future,
@@ -51,7 +40,8 @@ where
         Self {
             future,
             interval,
-            state: State::Suspended,
+            state: ParkState::Suspended,
+            completed: false,
         }
     }
 }
@@ -65,31 +55,90 @@ where
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut this = self.project();
-        loop {
-            match this.state {
-                State::Suspended => match ready!(this.interval.as_mut().poll_next(cx)) {
-                    Some(Parker::Park) => return Poll::Pending,
-                    Some(Parker::Unpark) => *this.state = State::Active,
-                    None => *this.state = State::NoChannel,
-                },
-                State::Active => {
-                    if let Poll::Ready(Some(Parker::Park)) = this.interval.as_mut().poll_next(cx) {
-                        *this.state = State::Suspended;
-                        return Poll::Pending;
-                    }
-                    let value = ready!(this.future.as_mut().poll(cx));
-                    *this.state = State::Completed;
-                    return Poll::Ready(value);
-                }
-                State::NoChannel => {
-                    let value = ready!(this.future.as_mut().poll(cx));
-                    *this.state = State::Completed;
-                    return Poll::Ready(value);
-                }
-                State::Completed => panic!("future polled after completing"),
-            }
+
+        if *this.completed {
+            return Poll::Pending;
         }
+
+        ready!(this.state.poll_active(this.interval.as_mut(), cx));
+        let value = ready!(this.future.as_mut().poll(cx));
+        *this.completed = true;
+        Poll::Ready(value)
+    }
+}
+
+impl<F, I> FusedFuture for Park<F, I>
+where
+    F: Future,
+    I: Stream<Item = Parker>,
+{
+    fn is_terminated(&self) -> bool {
+        self.completed
     }
 }
 
-// NOTE(yosh): we should probably test this, but I'm too tired today lol.
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::channel::{self, Parker};
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::future;
+
+    #[test]
+    fn starts_suspended() {
+        crate::utils::block_on(async {
+            let (send, recv) = channel::bounded(1);
+            let mut fut = future::ready("meow").park(recv);
+            let res = (&mut fut).timeout(Duration::from_millis(10)).await;
+            assert!(res.is_err());
+
+            send.send(Parker::Unpark).await.unwrap();
+            assert_eq!(fut.await, "meow");
+        })
+    }
+
+    #[test]
+    fn runs_when_channel_dropped() {
+        crate::utils::block_on(async {
+            let (send, recv) = channel::bounded::<Parker>(1);
+            drop(send);
+            assert_eq!(future::ready("meow").park(recv).await, "meow");
+        })
+    }
+
+    #[test]
+    fn park_while_active() {
+        crate::utils::block_on(async {
+            let (unparker, receiver) = channel::parker();
+            let mut fut = Box::pin(
+                async { "meow" }
+                    .delay(Duration::from_millis(20))
+                    .park(receiver),
+            );
+            unparker.unpark();
+            unparker.park();
+            let res = fut.as_mut().timeout(Duration::from_millis(50)).await;
+            assert!(res.is_err());
+
+            unparker.unpark();
+            assert_eq!(fut.await, "meow");
+        })
+    }
+
+    #[test]
+    fn unpark_from_sync_closure() {
+        crate::utils::block_on(async {
+            let (unparker, receiver) = channel::parker();
+            let fut = async { "meow" }.park(receiver);
+            unparker.park();
+
+            let wake = move || unparker.unpark_now();
+            let handle = std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                wake()
+            });
+            assert_eq!(fut.await, "meow");
+            assert!(handle.join().unwrap());
+        })
+    }
+}
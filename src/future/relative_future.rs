@@ -1,4 +1,7 @@
-use std::{future::Future, pin::Pin};
+use core::ops::DerefMut;
+use core::{future::Future, pin::Pin};
+
+use crate::time::Duration;
 
 /// A future which holds a deadline relative to now.
 ///
@@ -11,4 +14,38 @@ pub trait Timer: Future {
     /// future. If the future has already resolved before, calling this method
     /// will allow it to resolve again.
     fn reset_timer(self: Pin<&mut Self>);
+
+    /// Returns the duration the future is moved forward by when it is reset,
+    /// if it is known.
+    ///
+    /// This is used to report the configured duration in a
+    /// [`TimeoutError`](crate::future::TimeoutError). The default
+    /// implementation returns `None`.
+    fn timer_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl<T: ?Sized + Timer + Unpin> Timer for &mut T {
+    fn reset_timer(mut self: Pin<&mut Self>) {
+        Pin::new(&mut **self).reset_timer()
+    }
+
+    fn timer_duration(&self) -> Option<Duration> {
+        (**self).timer_duration()
+    }
+}
+
+impl<P> Timer for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: Timer,
+{
+    fn reset_timer(self: Pin<&mut Self>) {
+        self.get_mut().as_mut().reset_timer()
+    }
+
+    fn timer_duration(&self) -> Option<Duration> {
+        (**self).timer_duration()
+    }
 }
@@ -0,0 +1,220 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use crate::task::{sleep, Sleep};
+use crate::time::{Duration, Instant};
+
+/// Retry a fallible operation, sleeping between attempts.
+///
+/// `op` is called to create a new attempt each time the previous attempt
+/// failed. The returned future resolves to the output of the first attempt
+/// which returns `Ok`, or to the error of the last attempt once no more retries
+/// are left.
+///
+/// At most `retries` retries are made after the first attempt, so passing `0`
+/// runs the operation exactly once. Before each retry the next duration from
+/// `backoff` is slept; when `backoff` runs out no more retries are made.
+/// Use [`std::iter::repeat`] for a fixed delay between attempts, or
+/// [`time::backoff`](crate::time::backoff) to grow the delay after each
+/// attempt.
+///
+/// # Example
+///
+/// ```
+/// use futures_time::future;
+/// use futures_time::time::Duration;
+/// use std::iter;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let mut attempts = 0;
+///         let backoff = iter::successors(Some(Duration::from_millis(1)), |d| Some(*d * 2));
+///         let res: Result<&str, &str> = future::retry(3, backoff, || {
+///             attempts += 1;
+///             let attempt = attempts;
+///             async move { if attempt < 3 { Err("nope") } else { Ok("meow") } }
+///         })
+///         .await;
+///         assert_eq!(res, Ok("meow"));
+///         assert_eq!(attempts, 3);
+///     });
+/// }
+/// ```
+pub fn retry<F, Fut, T, E, B>(retries: usize, backoff: B, op: F) -> Retry<F, Fut, B::IntoIter>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    B: IntoIterator<Item = Duration>,
+{
+    Retry {
+        op,
+        attempt: None,
+        sleep: None,
+        backoff: backoff.into_iter(),
+        retries,
+        deadline: None,
+        completed: false,
+    }
+}
+
+pin_project! {
+    /// Retries a fallible operation, sleeping between attempts.
+    ///
+    /// This `struct` is created by the [`retry`] function. See its
+    /// documentation for more.
+    ///
+    /// [`retry`]: crate::future::retry
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct Retry<F, Fut, B> {
+        op: F,
+        #[pin]
+        attempt: Option<Fut>,
+        sleep: Option<Sleep>,
+        backoff: B,
+        retries: usize,
+        deadline: Option<Instant>,
+        completed: bool,
+    }
+}
+
+impl<F, Fut, B> Retry<F, Fut, B> {
+    /// Stop retrying once `deadline` would be passed.
+    ///
+    /// A retry is only scheduled if its backoff ends before `deadline`.
+    /// Otherwise the error of the last attempt is returned right away, rather
+    /// than sleeping first.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
+
+impl<F, Fut, T, E, B> Future for Retry<F, Fut, B>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    B: Iterator<Item = Duration>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if *this.completed {
+            return Poll::Pending;
+        }
+
+        loop {
+            if let Some(sleep) = this.sleep.as_mut() {
+                ready!(Pin::new(sleep).poll(cx));
+                *this.sleep = None;
+            }
+
+            if this.attempt.is_none() {
+                this.attempt.set(Some((this.op)()));
+            }
+
+            let res = ready!(this.attempt.as_mut().as_pin_mut().unwrap().poll(cx));
+            this.attempt.set(None);
+
+            let err = match res {
+                Ok(value) => {
+                    *this.completed = true;
+                    return Poll::Ready(Ok(value));
+                }
+                Err(err) => err,
+            };
+
+            let dur = match *this.retries {
+                0 => None,
+                _ => this.backoff.next(),
+            };
+            let dur = match (dur, *this.deadline) {
+                (Some(dur), Some(deadline)) if Instant::now().saturating_add(dur) > deadline => None,
+                (dur, _) => dur,
+            };
+            match dur {
+                Some(dur) => {
+                    *this.retries -= 1;
+                    *this.sleep = Some(sleep(dur));
+                }
+                None => {
+                    *this.completed = true;
+                    return Poll::Ready(Err(err));
+                }
+            }
+        }
+    }
+}
+
+impl<F, Fut, T, E, B> FusedFuture for Retry<F, Fut, B>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    B: Iterator<Item = Duration>,
+{
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::future;
+    use crate::time::{Duration, Instant};
+    use std::iter;
+
+    #[test]
+    fn zero_retries() {
+        crate::utils::block_on(async {
+            let mut attempts = 0;
+            let backoff = iter::repeat(Duration::from_millis(1));
+            let res: Result<(), usize> = future::retry(0, backoff, || {
+                attempts += 1;
+                let attempt = attempts;
+                async move { Err(attempt) }
+            })
+            .await;
+            assert_eq!(res, Err(1));
+        })
+    }
+
+    #[test]
+    fn returns_last_error() {
+        crate::utils::block_on(async {
+            let mut attempts = 0;
+            let backoff = iter::repeat(Duration::from_millis(1));
+            let res: Result<(), usize> = future::retry(2, backoff, || {
+                attempts += 1;
+                let attempt = attempts;
+                async move { Err(attempt) }
+            })
+            .await;
+            assert_eq!(res, Err(3));
+        })
+    }
+
+    #[test]
+    fn deadline_short_circuits() {
+        crate::utils::block_on(async {
+            let now = Instant::now();
+            let mut attempts = 0;
+            let backoff = iter::repeat(Duration::from_secs(10));
+            let res: Result<(), usize> = future::retry(5, backoff, || {
+                attempts += 1;
+                let attempt = attempts;
+                async move { Err(attempt) }
+            })
+            .deadline(now + Duration::from_secs(1))
+            .await;
+            assert_eq!(res, Err(1));
+            assert!(now.elapsed() < Duration::from_secs(1));
+        })
+    }
+}
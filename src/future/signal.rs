@@ -0,0 +1,233 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+
+use super::DynDeadline;
+use crate::channel::{self, Receiver, Sender};
+use crate::stream::IntoStream;
+use crate::time::Instant;
+
+/// Create a new signal.
+///
+/// A signal is a pair of a [`SignalSender`] and a [`SignalReceiver`]. The
+/// receiver is a future which resolves once the signal fires, and can be
+/// cloned to notify any number of tasks. The signal fires when
+/// [`SignalSender::notify`] is called, or when all senders have been dropped.
+///
+/// Because receivers are futures, they can be used as a deadline for methods
+/// such as [`FutureExt::timeout`] or [`StreamExt::take_until`]. Receivers can
+/// also be converted into a stream which yields once when the signal fires, so
+/// they can be used as the interval of methods such as [`StreamExt::sample`].
+///
+/// [`FutureExt::timeout`]: crate::future::FutureExt::timeout
+/// [`StreamExt::take_until`]: crate::stream::StreamExt::take_until
+/// [`StreamExt::sample`]: crate::stream::StreamExt::sample
+///
+/// # Example
+///
+/// ```
+/// use futures_time::future;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let (sender, receiver) = future::signal();
+///         let waiter = receiver.clone();
+///         sender.notify();
+///         waiter.await;
+///         receiver.await;
+///     });
+/// }
+/// ```
+pub fn signal() -> (SignalSender, SignalReceiver) {
+    let (sender, receiver) = channel::bounded(1);
+    (SignalSender { sender }, SignalReceiver { receiver })
+}
+
+/// The sending half of a signal.
+///
+/// This `struct` is created by the [`signal`] function. See its
+/// documentation for more.
+#[derive(Debug, Clone)]
+pub struct SignalSender {
+    sender: Sender<()>,
+}
+
+impl SignalSender {
+    /// Fire the signal, resolving all receivers.
+    ///
+    /// This does not require the sender to be dropped, so it can be called
+    /// from state which outlives the signal's waiters. Once fired, the signal
+    /// stays fired: receivers which are polled later resolve right away.
+    pub fn notify(&self) {
+        self.sender.close();
+    }
+
+    /// Returns `true` if the signal has fired.
+    pub fn is_notified(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+/// The receiving half of a signal.
+///
+/// This `struct` is created by the [`signal`] function. See its
+/// documentation for more.
+#[derive(Debug, Clone)]
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct SignalReceiver {
+    receiver: Receiver<()>,
+}
+
+impl SignalReceiver {
+    /// Returns `true` if the signal has fired.
+    ///
+    /// This checks the signal without waiting for it, which is useful to stop
+    /// at points where it is safe to do so, rather than wherever the receiver
+    /// happens to be awaited.
+    pub fn is_notified(&self) -> bool {
+        self.receiver.is_closed()
+    }
+}
+
+impl Future for SignalReceiver {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Nothing is ever sent, so the stream only ends once the channel has
+        // been closed by a `notify` call or by dropping all senders.
+        match Pin::new(&mut self.receiver).poll_next(cx) {
+            Poll::Ready(_) => Poll::Ready(()),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl DynDeadline for SignalReceiver {
+    fn poll_deadline(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.poll(cx)
+    }
+
+    /// Signals can't be moved, so this does nothing.
+    fn reset_deadline(self: Pin<&mut Self>, _at: Instant) {}
+}
+
+impl IntoStream for SignalReceiver {
+    type Item = ();
+    type IntoStream = SignalStream;
+
+    fn into_stream(self) -> Self::IntoStream {
+        SignalStream {
+            receiver: Some(self),
+        }
+    }
+}
+
+/// A stream which yields once when a signal fires, and then ends.
+///
+/// This `struct` is created by converting a [`SignalReceiver`] with
+/// [`IntoStream::into_stream`]. Methods such as [`StreamExt::sample`] do this
+/// when passed a receiver, emitting the latest value once the signal fires.
+///
+/// [`StreamExt::sample`]: crate::stream::StreamExt::sample
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::future;
+/// use futures_time::stream::IntoStream;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let (sender, receiver) = future::signal();
+///         sender.notify();
+///         let items: Vec<_> = receiver.into_stream().collect().await;
+///         assert_eq!(items, vec![()]);
+///     });
+/// }
+/// ```
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled or .awaited"]
+pub struct SignalStream {
+    receiver: Option<SignalReceiver>,
+}
+
+impl Stream for SignalStream {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let receiver = match &mut self.receiver {
+            Some(receiver) => receiver,
+            None => return Poll::Ready(None),
+        };
+        match Pin::new(receiver).poll(cx) {
+            Poll::Ready(()) => {
+                self.receiver = None;
+                Poll::Ready(Some(()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl FusedStream for SignalStream {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_none()
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::future::signal;
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::future;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn notify_without_drop() {
+        crate::utils::block_on(async {
+            let (sender, receiver) = signal();
+            let res = receiver.clone().timeout(Duration::from_millis(10)).await;
+            assert!(res.is_err());
+
+            assert!(!receiver.is_notified());
+            sender.notify();
+            assert!(sender.is_notified());
+            assert!(receiver.is_notified());
+            receiver.clone().await;
+            receiver.await;
+        })
+    }
+
+    #[test]
+    fn fires_on_drop() {
+        crate::utils::block_on(async {
+            let (sender, receiver) = signal();
+            drop(sender);
+            receiver.await;
+        })
+    }
+
+    #[test]
+    fn sample_on_signal() {
+        crate::utils::block_on(async {
+            let (sender, receiver) = signal();
+            let notify = async {
+                crate::task::sleep(Duration::from_millis(50)).await;
+                sender.notify();
+            };
+            let sampled = async {
+                crate::stream::interval(Duration::from_millis(20))
+                    .take(10)
+                    .sample(receiver)
+                    .next()
+                    .await
+            };
+            let ((), first) = future::zip(notify, sampled).await;
+            assert!(first.is_some());
+        })
+    }
+}
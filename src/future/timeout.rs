@@ -1,12 +1,19 @@
-use crate::utils::timeout_err;
-
-use std::future::Future;
-use std::io;
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
 
+use futures_core::future::FusedFuture;
 use pin_project_lite::pin_project;
 
+#[cfg(feature = "std")]
+use super::Deadline;
+use super::{TimeoutError, Timer};
+use crate::time::Duration;
+#[cfg(feature = "std")]
+use crate::time::Instant;
+use crate::utils::Stopwatch;
+
 pin_project! {
     /// A future that times out after a duration of time.
     ///
@@ -22,39 +29,254 @@ pin_project! {
         #[pin]
         deadline: D,
         completed: bool,
+        stopwatch: Stopwatch,
+        start: Option<fn(Pin<&mut D>)>,
+        configured: Option<Duration>,
     }
 }
 
 impl<F, D> Timeout<F, D> {
-    pub(super) fn new(future: F, deadline: D) -> Self {
+    pub(crate) fn new(future: F, deadline: D) -> Self {
         Self {
             future,
             deadline,
             completed: false,
+            stopwatch: Stopwatch::start(),
+            start: None,
+            configured: None,
         }
     }
+
+    /// Report `configured` as the configured duration of a timeout error.
+    pub(crate) fn with_configured(mut self, configured: Option<Duration>) -> Self {
+        self.configured = configured;
+        self
+    }
+}
+
+impl<F: fmt::Debug, D: fmt::Debug> fmt::Debug for Timeout<F, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeout")
+            .field("future", &self.future)
+            .field("deadline", &self.deadline)
+            .field("completed", &self.completed)
+            .finish()
+    }
+}
+
+impl<F, D: Timer> Timeout<F, D> {
+    /// Start counting down when the future is first polled, rather than when
+    /// the timeout was created.
+    ///
+    /// By default the deadline starts when [`timeout`] is called, so time
+    /// spent before the future is first polled, such as while it waits in a
+    /// queue, counts against it. With this option the timer is reset when the
+    /// future is first polled.
+    ///
+    /// [`timeout`]: crate::future::FutureExt::timeout
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::prelude::*;
+    /// use futures_time::task;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let fut = task::sleep(Duration::from_millis(20))
+    ///             .timeout(Duration::from_millis(100))
+    ///             .lazy_start();
+    ///         task::sleep(Duration::from_millis(150)).await;
+    ///         assert!(fut.await.is_ok());
+    ///     });
+    /// }
+    /// ```
+    pub fn lazy_start(mut self) -> Self {
+        self.start = Some(|deadline| deadline.reset_timer());
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F, D: Deadline> Timeout<F, D> {
+    /// Returns the instant at which this future times out.
+    pub fn deadline(&self) -> Instant {
+        self.deadline.deadline()
+    }
 }
 
 impl<F: Future, D: Future> Future for Timeout<F, D> {
-    type Output = io::Result<F::Output>;
+    type Output = Result<F::Output, TimeoutError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let this = self.project();
+        let mut this = self.project();
+
+        // Stay pending once completed, so this can be polled again from a
+        // selection loop which does not track completed branches.
+        if *this.completed {
+            return Poll::Pending;
+        }
 
-        assert!(!*this.completed, "future polled after completing");
+        if let Some(start) = this.start.take() {
+            start(this.deadline.as_mut());
+            this.stopwatch.restart();
+        }
 
         match this.future.poll(cx) {
             Poll::Ready(v) => {
                 *this.completed = true;
                 Poll::Ready(Ok(v))
             }
-            Poll::Pending => match this.deadline.poll(cx) {
+            Poll::Pending => match this.deadline.as_mut().poll(cx) {
                 Poll::Ready(_) => {
+                    trace!(elapsed = ?this.stopwatch.elapsed(), "future timed out");
                     *this.completed = true;
-                    Poll::Ready(Err(timeout_err("future timed out")))
+                    Poll::Ready(Err(TimeoutError::new(
+                        this.stopwatch.try_elapsed(),
+                        *this.configured,
+                    )))
                 }
                 Poll::Pending => Poll::Pending,
             },
         }
     }
 }
+
+impl<F: Future, D: Future> FusedFuture for Timeout<F, D> {
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use core::future::Future;
+    use core::task::Poll;
+    use futures_core::future::FusedFuture;
+    use futures_lite::future;
+
+    #[test]
+    fn is_terminated() {
+        crate::utils::block_on(async {
+            let mut fut = future::ready("meow").timeout(Duration::from_millis(10));
+            assert!(!fut.is_terminated());
+            assert_eq!((&mut fut).await.unwrap(), "meow");
+            assert!(fut.is_terminated());
+        })
+    }
+
+    #[test]
+    fn zero_and_max_durations() {
+        crate::utils::block_on(async {
+            let res = future::pending::<()>().timeout(Duration::ZERO).await;
+            assert!(res.is_err());
+
+            let mut fut = future::pending::<()>().timeout(Duration::MAX);
+            assert!(future::poll_once(&mut fut).await.is_none());
+            let res = future::ready(1).timeout(Duration::MAX).await;
+            assert_eq!(res.unwrap(), 1);
+        })
+    }
+
+    #[test]
+    fn configured_duration() {
+        crate::utils::block_on(async {
+            let dur = Duration::from_millis(10);
+            let err = future::pending::<()>().timeout(dur).await.unwrap_err();
+            assert_eq!(err.configured(), Some(dur));
+
+            let deadline = crate::time::Instant::now() + dur;
+            let err = future::pending::<()>().timeout(deadline).await.unwrap_err();
+            assert_eq!(err.configured(), None);
+        })
+    }
+
+    #[test]
+    fn deadline() {
+        crate::utils::block_on(async {
+            let start = crate::time::Instant::now();
+            let fut = future::pending::<()>().timeout(Duration::from_secs(10));
+            let deadline = fut.deadline();
+            assert!(deadline >= start + Duration::from_secs(10));
+            assert!(deadline <= crate::time::Instant::now() + Duration::from_secs(10));
+        })
+    }
+
+    #[test]
+    fn shared_budget() {
+        crate::utils::block_on(async {
+            let mut budget = crate::task::sleep(Duration::from_millis(50));
+            let deadline = budget.deadline();
+            let first = crate::task::sleep(Duration::from_millis(10))
+                .timeout(&mut budget)
+                .await;
+            assert!(first.is_ok());
+
+            // The second operation gets what is left of the same budget.
+            let second = future::pending::<()>().timeout(&mut budget);
+            assert_eq!(second.deadline(), deadline);
+            assert!(second.await.is_err());
+            assert!(budget.is_terminated());
+
+            let third = crate::task::sleep(Duration::from_millis(10))
+                .timeout(&mut budget)
+                .await;
+            assert!(third.is_err());
+        })
+    }
+
+    #[test]
+    fn poll_after_completion() {
+        crate::utils::block_on(async {
+            let mut fut = future::ready("meow").timeout(Duration::from_millis(10));
+            assert_eq!((&mut fut).await.unwrap(), "meow");
+            assert!(future::poll_once(&mut fut).await.is_none());
+
+            let mut fut = future::pending::<()>().timeout(Duration::ZERO);
+            assert!((&mut fut).await.is_err());
+            assert!(future::poll_once(&mut fut).await.is_none());
+        })
+    }
+
+    #[test]
+    fn select_loop() {
+        crate::utils::block_on(async {
+            let timeout = Duration::from_secs(1);
+            let mut fast = Box::pin(
+                future::ready(1)
+                    .delay(Duration::from_millis(5))
+                    .timeout(timeout),
+            );
+            let mut slow = Box::pin(
+                future::ready(2)
+                    .delay(Duration::from_millis(20))
+                    .timeout(timeout),
+            );
+
+            // Poll both branches every time, including the one which has
+            // already completed.
+            let mut results = vec![];
+            while results.len() < 2 {
+                future::poll_fn(|cx| {
+                    let len = results.len();
+                    if let Poll::Ready(res) = fast.as_mut().poll(cx) {
+                        results.push(res.unwrap());
+                    }
+                    if let Poll::Ready(res) = slow.as_mut().poll(cx) {
+                        results.push(res.unwrap());
+                    }
+                    match results.len() > len {
+                        true => Poll::Ready(()),
+                        false => Poll::Pending,
+                    }
+                })
+                .await;
+            }
+            assert_eq!(results, vec![1, 2]);
+            assert!(fast.is_terminated() && slow.is_terminated());
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use core::fmt;
+
+use crate::time::Duration;
+
+/// An error returned when a timeout elapses.
+///
+/// This error is returned by [`Future::timeout`] and [`Stream::timeout`]. When
+/// the `std` feature is enabled it can be converted into an [`io::Error`] with
+/// the [`io::ErrorKind::TimedOut`] kind.
+///
+/// [`Future::timeout`]: crate::future::FutureExt::timeout
+/// [`Stream::timeout`]: crate::stream::StreamExt::timeout
+/// [`io::Error`]: std::io::Error
+/// [`io::ErrorKind::TimedOut`]: std::io::ErrorKind::TimedOut
+///
+/// The error records how long the operation ran before timing out, and the
+/// duration the timeout was configured with when it is known. Both are
+/// included in its `Display` output, such as `timed out after 5.01s (budget
+/// 5s)`.
+///
+/// # Example
+///
+/// ```
+/// use futures_time::prelude::*;
+/// use futures_time::time::Duration;
+/// use std::io;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let err = async { "meow" }
+///             .delay(Duration::from_millis(100))
+///             .timeout(Duration::from_millis(50))
+///             .await
+///             .unwrap_err();
+///         assert!(err.elapsed().unwrap() >= Duration::from_millis(50));
+///         assert_eq!(io::Error::from(err).kind(), io::ErrorKind::TimedOut);
+///     });
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutError {
+    elapsed: Option<Duration>,
+    configured: Option<Duration>,
+}
+
+impl TimeoutError {
+    pub(crate) fn new(elapsed: Option<Duration>, configured: Option<Duration>) -> Self {
+        Self {
+            elapsed,
+            configured,
+        }
+    }
+
+    /// Returns how long the operation ran before it timed out.
+    ///
+    /// For streams this is measured from the previous item, or from the
+    /// previous timeout. This returns `None` when the `std` feature is
+    /// disabled, since there is no clock to measure with.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    /// Returns the duration the timeout was configured with, if it is known.
+    ///
+    /// This is known when the deadline reports its duration through
+    /// [`Timer::timer_duration`] or [`IntoFuture::configured_duration`], such
+    /// as a [`Duration`] passed to [`StreamExt::timeout`] or
+    /// [`FutureExt::timeout`]. Arbitrary futures can be used as deadlines as
+    /// well, which have no configured duration.
+    ///
+    /// [`Timer::timer_duration`]: crate::future::Timer::timer_duration
+    /// [`IntoFuture::configured_duration`]: crate::future::IntoFuture::configured_duration
+    /// [`StreamExt::timeout`]: crate::stream::StreamExt::timeout
+    /// [`FutureExt::timeout`]: crate::future::FutureExt::timeout
+    pub fn configured(&self) -> Option<Duration> {
+        self.configured
+    }
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.elapsed, self.configured) {
+            (Some(elapsed), Some(configured)) => {
+                write!(f, "timed out after {:?} (budget {:?})", elapsed, configured)
+            }
+            (Some(elapsed), None) => write!(f, "timed out after {:?}", elapsed),
+            (None, Some(configured)) => write!(f, "timed out (budget {:?})", configured),
+            (None, None) => f.write_str("deadline has elapsed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimeoutError {}
+
+#[cfg(feature = "std")]
+impl From<TimeoutError> for std::io::Error {
+    fn from(err: TimeoutError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::TimedOut, err)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::TimeoutError;
+    use crate::time::Duration;
+
+    #[test]
+    fn display() {
+        let elapsed = Some(Duration::from_millis(5010));
+        let configured = Some(Duration::from_secs(5));
+        assert_eq!(
+            TimeoutError::new(elapsed, configured).to_string(),
+            "timed out after 5.01s (budget 5s)"
+        );
+        assert_eq!(
+            TimeoutError::new(elapsed, None).to_string(),
+            "timed out after 5.01s"
+        );
+        assert_eq!(
+            TimeoutError::new(None, None).to_string(),
+            "deadline has elapsed"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use super::{Timeout, TimeoutError};
+use crate::task::Sleep;
+use crate::time::{Duration, Instant};
+
+/// How a [`TimeoutInstrumented`] future resolved.
+///
+/// This is passed to the observer of [`timeout_instrumented`] once the future
+/// resolves.
+///
+/// [`timeout_instrumented`]: crate::future::FutureExt::timeout_instrumented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutOutcome {
+    /// The future completed before the deadline.
+    Completed {
+        /// The time between the first poll and completion.
+        elapsed: Duration,
+    },
+    /// The deadline passed before the future completed.
+    TimedOut {
+        /// The duration the future was given.
+        budget: Duration,
+    },
+}
+
+pin_project! {
+    /// A future that times out after a duration of time, and reports how it
+    /// resolved.
+    ///
+    /// This `struct` is created by the [`timeout_instrumented`] method on [`FutureExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_instrumented`]: crate::future::FutureExt::timeout_instrumented
+    /// [`FutureExt`]: crate::future::FutureExt
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct TimeoutInstrumented<F, O> {
+        #[pin]
+        timeout: Timeout<F, Sleep>,
+        observer: Option<O>,
+        budget: Duration,
+        start: Option<Instant>,
+    }
+}
+
+impl<F, O> TimeoutInstrumented<F, O> {
+    pub(super) fn new(future: F, budget: Duration, observer: O) -> Self {
+        Self {
+            // The budget is counted from the first poll, like the elapsed time.
+            timeout: Timeout::new(future, crate::task::sleep(budget))
+                .with_configured(Some(budget))
+                .lazy_start(),
+            observer: Some(observer),
+            budget,
+            start: None,
+        }
+    }
+}
+
+impl<F: fmt::Debug, O> fmt::Debug for TimeoutInstrumented<F, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeoutInstrumented")
+            .field("timeout", &self.timeout)
+            .field("budget", &self.budget)
+            .field("start", &self.start)
+            .finish()
+    }
+}
+
+impl<F, O> Future for TimeoutInstrumented<F, O>
+where
+    F: Future,
+    O: FnOnce(TimeoutOutcome),
+{
+    type Output = Result<F::Output, TimeoutError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.observer.is_none() {
+            return Poll::Pending;
+        }
+
+        let start = *this.start.get_or_insert_with(Instant::now);
+        let res = ready!(this.timeout.poll(cx));
+        let outcome = match res {
+            Ok(_) => TimeoutOutcome::Completed {
+                elapsed: start.elapsed(),
+            },
+            Err(_) => TimeoutOutcome::TimedOut {
+                budget: *this.budget,
+            },
+        };
+        if let Some(observer) = this.observer.take() {
+            observer(outcome);
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl<F, O> FusedFuture for TimeoutInstrumented<F, O>
+where
+    F: Future,
+    O: FnOnce(TimeoutOutcome),
+{
+    fn is_terminated(&self) -> bool {
+        self.observer.is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeoutOutcome;
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::future;
+    use std::cell::Cell;
+
+    #[test]
+    fn reports_completion() {
+        crate::utils::block_on(async {
+            let outcome = Cell::new(None);
+            let res = async { "meow" }
+                .delay(Duration::from_millis(10))
+                .timeout_instrumented(Duration::from_secs(1), |o| outcome.set(Some(o)))
+                .await;
+            assert_eq!(res.unwrap(), "meow");
+            match outcome.get() {
+                Some(TimeoutOutcome::Completed { elapsed }) => {
+                    assert!(elapsed >= Duration::from_millis(10));
+                    assert!(elapsed < Duration::from_secs(1));
+                }
+                other => panic!("unexpected outcome: {:?}", other),
+            }
+        })
+    }
+
+    #[test]
+    fn starts_on_first_poll() {
+        crate::utils::block_on(async {
+            let outcome = Cell::new(None);
+            let fut = async { "meow" }
+                .delay(Duration::from_millis(10))
+                .timeout_instrumented(Duration::from_millis(50), |o| outcome.set(Some(o)));
+            crate::task::sleep(Duration::from_millis(100)).await;
+
+            assert_eq!(fut.await.unwrap(), "meow");
+            match outcome.get() {
+                Some(TimeoutOutcome::Completed { elapsed }) => {
+                    assert!(elapsed < Duration::from_millis(50));
+                }
+                other => panic!("unexpected outcome: {:?}", other),
+            }
+        })
+    }
+
+    #[test]
+    fn debug() {
+        crate::utils::block_on(async {
+            let fut = future::ready(1).timeout_instrumented(Duration::from_secs(1), |_| {});
+            assert!(format!("{:?}", fut).starts_with("TimeoutInstrumented"));
+        })
+    }
+
+    #[test]
+    fn reports_timeout_once() {
+        crate::utils::block_on(async {
+            let calls = Cell::new(0);
+            let budget = Duration::from_millis(10);
+            let mut fut = future::pending::<()>().timeout_instrumented(budget, |o| {
+                assert_eq!(o, TimeoutOutcome::TimedOut { budget });
+                calls.set(calls.get() + 1);
+            });
+            assert!((&mut fut).await.is_err());
+            assert!(future::poll_once(&mut fut).await.is_none());
+            assert_eq!(calls.get(), 1);
+        })
+    }
+}
@@ -0,0 +1,118 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use pin_project_lite::pin_project;
+
+/// The reason a [`TimeoutOrCancel`] future did not complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interrupted {
+    /// The deadline elapsed before the future completed.
+    TimedOut,
+    /// The future was cancelled before it completed.
+    Cancelled,
+}
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Interrupted::TimedOut => f.write_str("deadline has elapsed"),
+            Interrupted::Cancelled => f.write_str("future was cancelled"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Interrupted {}
+
+pin_project! {
+    /// A future that times out after a duration of time, or when it is
+    /// cancelled.
+    ///
+    /// This `struct` is created by the [`timeout_or_cancel`] method on [`FutureExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_or_cancel`]: crate::future::FutureExt::timeout_or_cancel
+    /// [`FutureExt`]: crate::future::FutureExt
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct TimeoutOrCancel<F, D, C> {
+        #[pin]
+        future: F,
+        #[pin]
+        deadline: D,
+        #[pin]
+        cancel: C,
+        completed: bool,
+    }
+}
+
+impl<F, D, C> TimeoutOrCancel<F, D, C> {
+    pub(super) fn new(future: F, deadline: D, cancel: C) -> Self {
+        Self {
+            future,
+            deadline,
+            cancel,
+            completed: false,
+        }
+    }
+}
+
+impl<F: Future, D: Future, C: Future> Future for TimeoutOrCancel<F, D, C> {
+    type Output = Result<F::Output, Interrupted>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if *this.completed {
+            return Poll::Pending;
+        }
+
+        let res = if let Poll::Ready(v) = this.future.poll(cx) {
+            Ok(v)
+        } else if this.cancel.poll(cx).is_ready() {
+            Err(Interrupted::Cancelled)
+        } else if this.deadline.poll(cx).is_ready() {
+            Err(Interrupted::TimedOut)
+        } else {
+            return Poll::Pending;
+        };
+
+        *this.completed = true;
+        Poll::Ready(res)
+    }
+}
+
+impl<F: Future, D: Future, C: Future> FusedFuture for TimeoutOrCancel<F, D, C> {
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use super::Interrupted;
+    use crate::future;
+    use crate::prelude::*;
+    use crate::time::Duration;
+
+    #[test]
+    fn distinguishes_reasons() {
+        crate::utils::block_on(async {
+            let (_sender, receiver) = future::signal();
+            let res = futures_lite::future::pending::<()>()
+                .timeout_or_cancel(Duration::from_millis(10), receiver)
+                .await;
+            assert_eq!(res, Err(Interrupted::TimedOut));
+
+            let (sender, receiver) = future::signal();
+            sender.notify();
+            let res = futures_lite::future::pending::<()>()
+                .timeout_or_cancel(Duration::from_secs(3600), receiver)
+                .await;
+            assert_eq!(res, Err(Interrupted::Cancelled));
+        })
+    }
+}
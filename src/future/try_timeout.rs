@@ -0,0 +1,105 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::future::FusedFuture;
+use pin_project_lite::pin_project;
+
+use super::TimeoutError;
+use crate::time::Duration;
+use crate::utils::Stopwatch;
+
+pin_project! {
+    /// A future that times out after a duration of time, handing back the
+    /// unfinished future.
+    ///
+    /// This `struct` is created by the [`try_timeout`] method on [`FutureExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`try_timeout`]: crate::future::FutureExt::try_timeout
+    /// [`FutureExt`]: crate::future::FutureExt
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless polled or .awaited"]
+    pub struct TryTimeout<F, D> {
+        future: Option<F>,
+        #[pin]
+        deadline: D,
+        stopwatch: Stopwatch,
+        configured: Option<Duration>,
+    }
+}
+
+impl<F, D> TryTimeout<F, D> {
+    pub(super) fn new(future: F, deadline: D) -> Self {
+        Self {
+            future: Some(future),
+            deadline,
+            stopwatch: Stopwatch::start(),
+            configured: None,
+        }
+    }
+
+    /// Report `configured` as the configured duration of a timeout error.
+    pub(super) fn with_configured(mut self, configured: Option<Duration>) -> Self {
+        self.configured = configured;
+        self
+    }
+}
+
+impl<F: Future + Unpin, D: Future> Future for TryTimeout<F, D> {
+    type Output = Result<F::Output, (TimeoutError, F)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let future = match this.future.as_mut() {
+            Some(future) => future,
+            None => return Poll::Pending,
+        };
+
+        match Pin::new(future).poll(cx) {
+            Poll::Ready(v) => {
+                *this.future = None;
+                Poll::Ready(Ok(v))
+            }
+            Poll::Pending => match this.deadline.poll(cx) {
+                Poll::Ready(_) => {
+                    trace!(elapsed = ?this.stopwatch.elapsed(), "future timed out");
+                    let err = TimeoutError::new(this.stopwatch.try_elapsed(), *this.configured);
+                    Poll::Ready(Err((err, this.future.take().unwrap())))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+impl<F: Future + Unpin, D: Future> FusedFuture for TryTimeout<F, D> {
+    fn is_terminated(&self) -> bool {
+        self.future.is_none()
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_core::future::FusedFuture;
+
+    #[test]
+    fn hands_back_future() {
+        crate::utils::block_on(async {
+            let fut = crate::task::sleep(Duration::from_millis(40));
+            let (err, fut) = fut
+                .try_timeout(Duration::from_millis(10))
+                .await
+                .unwrap_err();
+            assert_eq!(err.configured(), Some(Duration::from_millis(10)));
+
+            let mut retry = fut.try_timeout(Duration::from_secs(1));
+            assert!(!retry.is_terminated());
+            assert!((&mut retry).await.is_ok());
+            assert!(retry.is_terminated());
+        })
+    }
+}
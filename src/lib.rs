@@ -86,6 +86,10 @@
 //! [`task::Sleep`], which is created from a `Duration.` This is in contrast
 //! with [`task::sleep_until`], which takes an `Instant`, and cannot be reset.
 //!
+//! To move a deadline by an arbitrary amount rather than by the duration it
+//! was created with, [`task::Sleep`] also implements the [`future::Deadline`]
+//! trait.
+//!
 //! # Cancellation
 //!
 //! You can use [`channel::bounded`] to create a [`channel::Sender`] and [`channel::Receiver`] pair.
@@ -135,43 +139,64 @@
 //! - [`Stream::timeout`](`stream::StreamExt::timeout`) Cancel the stream if the execution takes longer than the specified time.
 //! - [`stream::interval`](`stream::interval`) Creates a new stream that yields at a set interval.
 //!
+//! # Features
+//!
+//! - `std` _(default)_ enables [`time::Instant`], [`time::Clock`], and the channel re-exports.
+//!   Without it this crate is `no_std` and only requires `alloc`; every timer backend implies `std`.
+//! - `async-io` _(default)_ provides a timer backend using the [`async-io`] reactor.
+//! - `tokio` provides a timer backend using [`tokio::time`].
+//! - `wasm` provides a timer backend for `wasm32` targets running in the browser, using
+//!   [`gloo-timers`]. On those targets [`time::Instant`] reads the time from `performance.now()`.
+//! - `serde` implements `Serialize` and `Deserialize` for [`time::Duration`] and [`time::Instant`].
+//! - `chrono` adds `time::duration_until` and `task::sleep_until_datetime` to schedule
+//!   timers against wall-clock times from [`chrono`].
+//! - `tracing` emits [`tracing`] events at the `TRACE` level when a timeout fires, a debounce
+//!   emits or resets, an interval ticks, and a throttle drops an item. Events include the
+//!   elapsed time, and intervals also include their configured period.
+//!
+//! The first enabled timer backend in the order listed above becomes the
+//! [`time::SystemClock`], which is used by [`task::sleep`], [`stream::interval`], and the
+//! `IntoFuture` and `IntoStream` implementations of [`time::Duration`]. To use another backend
+//! than `async-io` as the default, disable this crate's default features. Without any timer
+//! backend only the combinators are available, and timers need to be supplied by implementing
+//! [`time::Clock`].
+//!
 //! # Re-exports
 //!
 //! - `channel` is a re-export of the [`async-channel`] crate, exposed for convenience
 //!
 //! [`async-channel`]: https://docs.rs/async-channel/latest/async_channel
+//! [`async-io`]: https://docs.rs/async-io/latest/async_io
+//! [`tokio::time`]: https://docs.rs/tokio/latest/tokio/time
+//! [`gloo-timers`]: https://docs.rs/gloo-timers/latest/gloo_timers
+//! [`tracing`]: https://docs.rs/tracing/latest/tracing
+//! [`chrono`]: https://docs.rs/chrono/latest/chrono
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(missing_debug_implementations)]
 #![warn(missing_docs, future_incompatible, unreachable_pub)]
 #![forbid(rustdoc::missing_doc_code_examples)]
 
+extern crate alloc;
+
+#[macro_use]
 pub(crate) mod utils;
 
+pub mod channel;
 pub mod future;
 pub mod stream;
 pub mod task;
 pub mod time;
 
-/// An async multi-producer multi-consumer channel.
-pub mod channel {
-    /// Suspend or resume execution of a future.
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-    pub enum Parker {
-        /// Put the future into a suspended state.
-        Park,
-        /// Put the future into an active state.
-        Unpark,
-    }
-    #[doc(inline)]
-    pub use async_channel::*;
-}
-
 /// The `futures-time` prelude.
 pub mod prelude {
+    #[cfg(feature = "std")]
+    pub use super::future::Deadline as _;
     pub use super::future::FutureExt as _;
     pub use super::future::IntoFuture as _;
     pub use super::future::Timer as _;
     pub use super::stream::IntoStream as _;
     pub use super::stream::StreamExt as _;
+    pub use super::time::DurationExt as _;
 }
@@ -0,0 +1,129 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::Timer;
+
+pin_project! {
+    /// Yield the last item received once a window, started by the first item,
+    /// has passed.
+    ///
+    /// This `struct` is created by the [`audit`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`audit`]: crate::stream::StreamExt::audit
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct Audit<S: Stream, D> {
+        #[pin]
+        stream: S,
+        #[pin]
+        deadline: D,
+        slot: Option<S::Item>,
+        state: State,
+    }
+}
+
+/// Internal state.
+#[derive(Debug)]
+enum State {
+    /// The underlying stream is yielding items.
+    Streaming,
+    /// The underlying stream has ended, but a final item may still need to be
+    /// yielded.
+    StreamDone,
+    /// The closing `Ready(None)` has been yielded.
+    Finished,
+}
+
+impl<S: Stream, D> Audit<S, D> {
+    pub(crate) fn new(stream: S, deadline: D) -> Self {
+        Self {
+            stream,
+            deadline,
+            slot: None,
+            state: State::Streaming,
+        }
+    }
+}
+
+impl<S, D> Stream for Audit<S, D>
+where
+    S: Stream,
+    D: Timer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if let State::Streaming = this.state {
+            loop {
+                match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(item)) => {
+                        // The first item of a window starts the timer, later
+                        // items only replace the value to yield.
+                        if this.slot.is_none() {
+                            this.deadline.as_mut().reset_timer();
+                        }
+                        *this.slot = Some(item);
+                    }
+                    Poll::Ready(None) => {
+                        *this.state = State::StreamDone;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        if this.slot.is_some() {
+            ready!(this.deadline.as_mut().poll(cx));
+            return Poll::Ready(this.slot.take());
+        }
+
+        match this.state {
+            State::Streaming => Poll::Pending,
+            State::StreamDone => {
+                *this.state = State::Finished;
+                Poll::Ready(None)
+            }
+            State::Finished => panic!("stream polled after completion"),
+        }
+    }
+}
+
+impl<S, D> FusedStream for Audit<S, D>
+where
+    S: Stream,
+    D: Timer,
+{
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Finished)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn one_item_per_burst() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = stream::iter(0..3)
+                .chain(stream::iter(3..6).delay(Duration::from_millis(100)))
+                .audit(Duration::from_millis(20))
+                .collect()
+                .await;
+
+            assert_eq!(items, vec![2, 5]);
+        })
+    }
+}
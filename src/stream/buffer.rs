@@ -1,10 +1,17 @@
-use std::mem;
-use std::pin::Pin;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+use core::pin::Pin;
 
 use pin_project_lite::pin_project;
 
 use core::task::{Context, Poll};
-use futures_core::stream::Stream;
+use futures_core::stream::{FusedStream, Stream};
+
+cfg_rt! {
+    use super::Interval;
+    use crate::time::Clock;
+}
 
 pin_project! {
     /// Buffer items and flushes them at each interval.
@@ -23,6 +30,8 @@ pin_project! {
         interval: I,
         slot: Vec<S::Item>,
         state: State,
+        capacity: Option<usize>,
+        reset: fn(Pin<&mut I>),
     }
 }
 
@@ -31,10 +40,96 @@ impl<S: Stream, I> Buffer<S, I> {
         Self {
             stream,
             interval,
-            slot: vec![],
+            slot: Vec::new(),
             state: State::Streaming,
+            capacity: None,
+            reset: |_| {},
         }
     }
+
+    /// Flush the buffer as soon as it holds `capacity` items, without waiting
+    /// for the next interval.
+    ///
+    /// The yielded `Vec`s never hold more than `capacity` items, so the latency
+    /// of an item is bounded both by the interval and by the number of items
+    /// that come after it. By default flushing early does not reset the
+    /// interval, which keeps firing at its own cadence; use
+    /// [`reset_on_flush`](Buffer::reset_on_flush) to restart it instead.
+    /// Buffers are allocated with room for `capacity` items up front.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let bufs: Vec<_> = stream::iter(0..5)
+    ///             .buffer(Duration::from_millis(100))
+    ///             .with_capacity(2)
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(bufs, vec![vec![0, 1], vec![2, 3], vec![4]]);
+    ///     })
+    /// }
+    /// ```
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "buffer capacity must be greater than zero");
+        self.slot.reserve(capacity.saturating_sub(self.slot.len()));
+        self.capacity = Some(capacity);
+        self
+    }
+}
+
+cfg_rt! {
+    impl<S: Stream, C: Clock> Buffer<S, Interval<C>>
+    where
+        Interval<C>: Unpin,
+    {
+        /// Restart the interval whenever the buffer is flushed early because it
+        /// is full.
+        ///
+        /// Without this, the items after an early flush are flushed at the
+        /// next tick of the original interval, which may come right away. With
+        /// it, every buffer gets a full interval to fill up.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use futures_lite::prelude::*;
+        /// use futures_time::prelude::*;
+        /// use futures_time::time::Duration;
+        /// use futures_lite::stream;
+        ///
+        /// fn main() {
+        ///     async_io::block_on(async {
+        ///         let bufs: Vec<_> = stream::iter(0..3)
+        ///             .buffer(Duration::from_millis(20))
+        ///             .with_capacity(2)
+        ///             .reset_on_flush()
+        ///             .collect()
+        ///             .await;
+        ///         assert_eq!(bufs, vec![vec![0, 1], vec![2]]);
+        ///     })
+        /// }
+        /// ```
+        pub fn reset_on_flush(mut self) -> Self {
+            self.reset = |interval| interval.get_mut().reset();
+            self
+        }
+    }
+}
+
+/// Take the buffered items, leaving a new buffer with room for `capacity` items.
+fn flush<T>(slot: &mut Vec<T>, capacity: Option<usize>) -> Vec<T> {
+    mem::replace(slot, Vec::with_capacity(capacity.unwrap_or(0)))
 }
 
 #[derive(Debug)]
@@ -43,6 +138,8 @@ enum State {
     Streaming,
     /// The underlying stream is done yielding items.
     StreamDone,
+    /// The interval is done, and items are yielded as soon as they arrive.
+    Passthrough,
     /// All timers have completed and all data has been yielded.
     TimerDone,
     /// The closing `Ready(None)` has been yielded.
@@ -61,7 +158,13 @@ impl<S: Stream, I: Stream> Stream for Buffer<S, I> {
                 // Poll the underlying stream until we get to `Poll::Pending`.
                 loop {
                     match this.stream.as_mut().poll_next(cx) {
-                        Poll::Ready(Some(value)) => this.slot.push(value),
+                        Poll::Ready(Some(value)) => {
+                            this.slot.push(value);
+                            if Some(this.slot.len()) == *this.capacity {
+                                (this.reset)(this.interval.as_mut());
+                                return Poll::Ready(Some(flush(this.slot, *this.capacity)));
+                            }
+                        }
                         Poll::Ready(None) => {
                             *this.state = State::StreamDone;
                             break;
@@ -71,20 +174,48 @@ impl<S: Stream, I: Stream> Stream for Buffer<S, I> {
                 }
 
                 // After the stream, always poll the interval timer.
-                this.interval.as_mut().poll_next(cx).map(move |_| {
-                    if let State::StreamDone = this.state {
-                        *this.state = State::TimerDone;
-                        cx.waker().wake_by_ref();
+                match this.interval.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(_)) => {
+                        if let State::StreamDone = this.state {
+                            *this.state = State::TimerDone;
+                            cx.waker().wake_by_ref();
+                        }
                     }
-                    Some(mem::take(&mut *this.slot))
-                })
+                    // The interval ended before the stream: flush one last
+                    // time, and pass the remaining items through right away.
+                    Poll::Ready(None) => match this.state {
+                        State::StreamDone => {
+                            *this.state = State::TimerDone;
+                            cx.waker().wake_by_ref();
+                        }
+                        _ => {
+                            *this.state = State::Passthrough;
+                            if this.slot.is_empty() {
+                                cx.waker().wake_by_ref();
+                                return Poll::Pending;
+                            }
+                        }
+                    },
+                    Poll::Pending => return Poll::Pending,
+                }
+                Poll::Ready(Some(flush(this.slot, *this.capacity)))
             }
 
+            // The interval has ended, but the underlying stream has not.
+            State::Passthrough => match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(value)) => Poll::Ready(Some(vec![value])),
+                Poll::Ready(None) => {
+                    *this.state = State::AllDone;
+                    Poll::Ready(None)
+                }
+                Poll::Pending => Poll::Pending,
+            },
+
             // The underlying stream is done yielding items.
             State::StreamDone => this.interval.as_mut().poll_next(cx).map(|_| {
                 cx.waker().wake_by_ref();
                 *this.state = State::TimerDone;
-                Some(mem::take(&mut *this.slot))
+                Some(flush(this.slot, *this.capacity))
             }),
 
             // All timers have completed and all data has been yielded.
@@ -99,15 +230,23 @@ impl<S: Stream, I: Stream> Stream for Buffer<S, I> {
     }
 }
 
-#[cfg(test)]
+impl<S: Stream, I: Stream> FusedStream for Buffer<S, I> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::AllDone)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
 mod test {
     use crate::prelude::*;
     use crate::time::Duration;
+    use futures_core::stream::FusedStream;
+    use futures_lite::future::poll_once;
     use futures_lite::prelude::*;
 
     #[test]
     fn buffer_all_values() {
-        async_io::block_on(async {
+        crate::utils::block_on(async {
             let interval = Duration::from_millis(5);
             let buffer = Duration::from_millis(20);
 
@@ -124,7 +263,7 @@ mod test {
 
     #[test]
     fn no_debounces_hit() {
-        async_io::block_on(async {
+        crate::utils::block_on(async {
             let interval = Duration::from_millis(20);
             let buffer = Duration::from_millis(10);
 
@@ -138,4 +277,76 @@ mod test {
             assert_eq!(counter, 10);
         })
     }
+
+    #[test]
+    fn flush_on_capacity() {
+        crate::utils::block_on(async {
+            let bufs: Vec<_> = crate::stream::interval(Duration::from_millis(5))
+                .take(5)
+                .buffer(Duration::from_millis(100))
+                .with_capacity(2)
+                .collect()
+                .await;
+
+            let lens: Vec<_> = bufs.iter().map(Vec::len).collect();
+            assert_eq!(lens, vec![2, 2, 1]);
+            assert!(bufs[0].capacity() >= 2);
+        })
+    }
+
+    #[test]
+    fn is_terminated() {
+        crate::utils::block_on(async {
+            let mut stream = crate::stream::interval(Duration::from_millis(5))
+                .take(2)
+                .buffer(Duration::from_millis(20));
+            assert!(!stream.is_terminated());
+            while stream.next().await.is_some() {}
+            assert!(stream.is_terminated());
+        })
+    }
+
+    #[test]
+    fn passthrough_after_interval_ends() {
+        crate::utils::block_on(async {
+            let trigger = crate::stream::interval_count(Duration::from_millis(30), 1);
+            let bufs: Vec<_> = crate::stream::interval(Duration::from_millis(20))
+                .take(4)
+                .buffer(trigger)
+                .collect()
+                .await;
+
+            assert_eq!(bufs.len(), 4);
+            assert_eq!(bufs[0].len(), 1);
+            assert!(bufs[1..].iter().all(|buf| buf.len() == 1));
+        })
+    }
+
+    #[test]
+    fn reset_on_flush() {
+        futures_lite::future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let period = Duration::from_millis(10);
+            let (sender, receiver) = crate::channel::unbounded();
+            let mut stream = receiver
+                .buffer(crate::stream::Interval::with_clock(period, clock.clone()))
+                .with_capacity(2)
+                .reset_on_flush();
+
+            // Fill the buffer halfway through the first interval.
+            clock.advance(Duration::from_millis(5));
+            sender.try_send(0).unwrap();
+            sender.try_send(1).unwrap();
+            assert_eq!(poll_once(stream.next()).await, Some(Some(vec![0, 1])));
+
+            // The original tick has passed, but the interval was restarted.
+            clock.advance(Duration::from_millis(5));
+            sender.try_send(2).unwrap();
+            assert_eq!(poll_once(stream.next()).await, None);
+
+            // A full period after the early flush, the next buffer is flushed.
+            clock.advance(Duration::from_millis(5));
+            assert_eq!(poll_once(stream.next()).await, Some(Some(vec![2])));
+        })
+    }
 }
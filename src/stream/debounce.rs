@@ -1,11 +1,12 @@
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use core::pin::Pin;
+use core::task::{Context, Poll};
 
 use futures_core::ready;
-use futures_core::stream::Stream;
+use futures_core::stream::{FusedStream, Stream};
 use pin_project_lite::pin_project;
 
 use crate::future::Timer;
+use crate::utils::Stopwatch;
 
 pin_project! {
     /// Debounce the stream.
@@ -24,6 +25,10 @@ pin_project! {
         deadline: D,
         slot: Option<S::Item>,
         state: State,
+        stopwatch: Stopwatch,
+        idle: bool,
+        leading: bool,
+        trailing: bool,
     }
 }
 
@@ -48,8 +53,83 @@ impl<S: Stream, D> Debounce<S, D> {
             deadline,
             slot: None,
             state: State::Streaming,
+            stopwatch: Stopwatch::start(),
+            idle: true,
+            leading: false,
+            trailing: true,
         }
     }
+
+    /// Yield the first item of each burst right away, and drop the rest.
+    ///
+    /// An item starts a new burst if no items were received for an entire
+    /// window before it. The window is still reset by every item, so a burst
+    /// only ends once the stream has been quiet for a full window.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::iter(0..3)
+    ///             .debounce(Duration::from_millis(20))
+    ///             .leading()
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![0]);
+    ///     })
+    /// }
+    /// ```
+    pub fn leading(mut self) -> Self {
+        self.leading = true;
+        self.trailing = false;
+        self
+    }
+
+    /// Yield the last item of each burst once the window expires, and drop the
+    /// rest.
+    ///
+    /// This is the default.
+    pub fn trailing(mut self) -> Self {
+        self.leading = false;
+        self.trailing = true;
+        self
+    }
+
+    /// Yield both the first item of each burst right away, and the last item
+    /// of each burst once the window expires.
+    ///
+    /// A burst of a single item only yields that item once, right away.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::iter(0..3)
+    ///             .debounce(Duration::from_millis(20))
+    ///             .both()
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![0, 2]);
+    ///     })
+    /// }
+    /// ```
+    pub fn both(mut self) -> Self {
+        self.leading = true;
+        self.trailing = true;
+        self
+    }
 }
 
 impl<S, D> Stream for Debounce<S, D>
@@ -62,33 +142,49 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
-        // See if we need to get more data from the stream.
-        if let State::Streaming = this.state {
-            match this.stream.poll_next(cx) {
-                Poll::Ready(Some(item)) => {
-                    *this.slot = Some(item);
+        while let State::Streaming = this.state {
+            // Emit a pending item once its window has expired, before the
+            // stream gets a chance to overwrite it with a newer item.
+            if !*this.idle && this.deadline.as_mut().poll(cx).is_ready() {
+                *this.idle = true;
+                if let Some(item) = this.slot.take() {
+                    trace!(elapsed = ?this.stopwatch.elapsed(), "debounce emit");
+                    return Poll::Ready(Some(item));
+                }
+            }
+
+            // See if we need to get more data from the stream.
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let leading = *this.idle && *this.leading;
+                    if !*this.idle {
+                        trace!(elapsed = ?this.stopwatch.elapsed(), "debounce reset");
+                    }
+                    *this.idle = false;
+                    this.stopwatch.restart();
                     this.deadline.as_mut().reset_timer();
+
+                    // The first item after a quiet window starts a new burst.
+                    if leading {
+                        trace!("debounce emit leading");
+                        return Poll::Ready(Some(item));
+                    } else if *this.trailing {
+                        *this.slot = Some(item);
+                    }
                 }
-                Poll::Ready(None) => match *this.slot {
+                None => match *this.slot {
                     Some(_) => *this.state = State::FinalItem,
                     None => *this.state = State::SendingNone,
                 },
-                _ => {}
-            };
+            }
         }
 
-        // Handle the timer.
+        // Handle the stream having ended.
         match this.state {
-            State::Streaming => match this.slot.is_some() {
-                true => {
-                    ready!(this.deadline.as_mut().poll(cx));
-                    Poll::Ready(this.slot.take())
-                }
-                false => Poll::Pending,
-            },
-
+            State::Streaming => unreachable!(),
             State::FinalItem => {
-                let _ = futures_core::ready!(this.deadline.as_mut().poll(cx));
+                let _ = ready!(this.deadline.as_mut().poll(cx));
+                trace!(elapsed = ?this.stopwatch.elapsed(), "debounce emit");
                 *this.state = State::SendingNone;
                 cx.waker().wake_by_ref();
                 Poll::Ready(this.slot.take())
@@ -103,15 +199,26 @@ where
     }
 }
 
-#[cfg(test)]
+impl<S, D> FusedStream for Debounce<S, D>
+where
+    S: Stream,
+    D: Timer,
+{
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Finished)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
 mod test {
     use crate::prelude::*;
     use crate::time::Duration;
+    use futures_core::stream::FusedStream;
     use futures_lite::prelude::*;
 
     #[test]
     fn all_values_debounce() {
-        async_io::block_on(async {
+        crate::utils::block_on(async {
             let interval = Duration::from_millis(10);
             let debounce = Duration::from_millis(20);
 
@@ -128,7 +235,7 @@ mod test {
 
     #[test]
     fn no_debounces_hit() {
-        async_io::block_on(async {
+        crate::utils::block_on(async {
             let interval = Duration::from_millis(40);
             let debounce = Duration::from_millis(10);
 
@@ -142,4 +249,125 @@ mod test {
             assert_eq!(counter, 10);
         })
     }
+
+    #[test]
+    fn is_terminated() {
+        crate::utils::block_on(async {
+            let mut stream = crate::stream::interval(Duration::from_millis(5))
+                .take(2)
+                .debounce(Duration::from_millis(20));
+            assert!(!stream.is_terminated());
+            while stream.next().await.is_some() {}
+            assert!(stream.is_terminated());
+        })
+    }
+
+    #[test]
+    fn emits_before_overwriting() {
+        use crate::task::sleep_until;
+        use crate::time::Instant;
+
+        crate::utils::block_on(async {
+            // The second item only becomes available after the window of the
+            // first item has expired. By the time the debounced stream is
+            // polled again both are ready, and the first item must not be lost.
+            let start = Instant::now();
+            let stream = futures_lite::stream::unfold(0, move |n| async move {
+                match n {
+                    0 => Some((1, 1)),
+                    1 => {
+                        sleep_until(start + Duration::from_millis(30)).await;
+                        Some((2, 2))
+                    }
+                    _ => None,
+                }
+            });
+            let mut stream = Box::pin(stream.debounce(Duration::from_millis(10)));
+
+            let res = stream.next().timeout(Duration::from_millis(1)).await;
+            assert!(res.is_err());
+            crate::task::sleep(Duration::from_millis(50)).await;
+
+            assert_eq!(stream.collect::<Vec<_>>().await, vec![1, 2]);
+        })
+    }
+
+    #[test]
+    fn custom_timer() {
+        use crate::future::Timer;
+        use crate::task::{sleep, Sleep};
+        use core::future::Future;
+        use core::pin::Pin;
+        use core::task::{Context, Poll};
+
+        /// A timer which counts how often it's been reset.
+        struct Counted {
+            sleep: Sleep,
+            resets: usize,
+        }
+
+        impl Future for Counted {
+            type Output = <Sleep as Future>::Output;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                Pin::new(&mut self.sleep).poll(cx)
+            }
+        }
+
+        impl Timer for Counted {
+            fn reset_timer(mut self: Pin<&mut Self>) {
+                self.resets += 1;
+                Pin::new(&mut self.sleep).reset_timer();
+            }
+        }
+
+        crate::utils::block_on(async {
+            let timer = Counted {
+                sleep: sleep(Duration::from_millis(20)),
+                resets: 0,
+            };
+            let mut stream = futures_lite::stream::iter(1..4).debounce(timer);
+            assert_eq!(stream.next().await, Some(3));
+            assert_eq!(stream.next().await, None);
+            assert_eq!(stream.deadline.resets, 3);
+        })
+    }
+
+    #[test]
+    fn leading_after_quiet() {
+        use crate::time::Instant;
+
+        crate::utils::block_on(async {
+            let window = Duration::from_millis(50);
+
+            // A single isolated item is yielded right away.
+            let start = Instant::now();
+            let mut stream = futures_lite::stream::once(1)
+                .chain(futures_lite::stream::pending())
+                .debounce(window)
+                .leading();
+            assert_eq!(stream.next().await, Some(1));
+            assert!(start.elapsed() < window);
+
+            // A burst yields its first item right away, and its last item once
+            // the window expires.
+            let start = Instant::now();
+            let mut stream = futures_lite::stream::iter(0..3).debounce(window).both();
+            assert_eq!(stream.next().await, Some(0));
+            assert!(start.elapsed() < window);
+            assert_eq!(stream.next().await, Some(2));
+            assert!(start.elapsed() >= window);
+            assert_eq!(stream.next().await, None);
+
+            // After the stream has been quiet for a window, the next item is
+            // yielded right away again.
+            let items: Vec<_> = futures_lite::stream::iter(vec![0, 1])
+                .chain(futures_lite::stream::once(2).delay(Duration::from_millis(100)))
+                .debounce(window)
+                .both()
+                .collect()
+                .await;
+            assert_eq!(items, vec![0, 1, 2]);
+        })
+    }
 }
@@ -0,0 +1,136 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::Timer;
+
+pin_project! {
+    /// Suppress consecutive equal items, but repeat the last item if nothing
+    /// was yielded for a duration.
+    ///
+    /// This `struct` is created by the [`dedup_timeout`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`dedup_timeout`]: crate::stream::StreamExt::dedup_timeout
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct DedupTimeout<S: Stream, D> {
+        #[pin]
+        stream: S,
+        #[pin]
+        deadline: D,
+        last: Option<S::Item>,
+        done: bool,
+    }
+}
+
+impl<S: Stream, D> DedupTimeout<S, D> {
+    pub(crate) fn new(stream: S, deadline: D) -> Self {
+        Self {
+            stream,
+            deadline,
+            last: None,
+            done: false,
+        }
+    }
+}
+
+impl<S, D> Stream for DedupTimeout<S, D>
+where
+    S: Stream,
+    S::Item: PartialEq + Clone,
+    D: Timer,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        // Poll the underlying stream until it yields a new value, skipping
+        // over values which are equal to the last one yielded.
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(value)) => {
+                    if this.last.as_ref() == Some(&value) {
+                        trace!("dedup_timeout skipped repeated item");
+                        continue;
+                    }
+                    *this.last = Some(value.clone());
+                    this.deadline.as_mut().reset_timer();
+                    return Poll::Ready(Some(value));
+                }
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        // Nothing new came in: repeat the last value once the deadline passes.
+        let last = match this.last {
+            Some(last) => last,
+            None => return Poll::Pending,
+        };
+        match this.deadline.as_mut().poll(cx) {
+            Poll::Ready(_) => {
+                let value = last.clone();
+                this.deadline.as_mut().reset_timer();
+                Poll::Ready(Some(value))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<S, D> FusedStream for DedupTimeout<S, D>
+where
+    S: Stream,
+    S::Item: PartialEq + Clone,
+    D: Timer,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::{Duration, Instant};
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn skips_repeated_items() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = stream::iter(vec![1, 1, 2, 2, 2, 1, 3, 3])
+                .dedup_timeout(Duration::from_secs(1))
+                .collect()
+                .await;
+            assert_eq!(items, vec![1, 2, 1, 3]);
+        })
+    }
+
+    #[test]
+    fn repeats_when_unchanged() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let items: Vec<_> = stream::once("meow")
+                .chain(stream::pending())
+                .dedup_timeout(Duration::from_millis(20))
+                .take(3)
+                .collect()
+                .await;
+
+            assert_eq!(items, vec!["meow"; 3]);
+            assert!(start.elapsed() >= Duration::from_millis(40));
+        })
+    }
+}
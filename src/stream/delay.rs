@@ -1,8 +1,8 @@
-use std::future::Future;
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
 
-use futures_core::stream::Stream;
+use futures_core::stream::{FusedStream, Stream};
 use pin_project_lite::pin_project;
 
 pin_project! {
@@ -52,7 +52,7 @@ where
 
         match this.state {
             State::Timer => match this.deadline.poll(cx) {
-                Poll::Pending => return Poll::Pending,
+                Poll::Pending => Poll::Pending,
                 Poll::Ready(_) => {
                     *this.state = State::Streaming;
                     this.stream.poll_next(cx)
@@ -62,3 +62,37 @@ where
         }
     }
 }
+
+impl<S, D> FusedStream for Delay<S, D>
+where
+    S: FusedStream,
+    D: Future,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::{Duration, Instant};
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn duration_and_instant() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            stream::once(())
+                .delay(Duration::from_millis(20))
+                .next()
+                .await;
+            assert!(start.elapsed() >= Duration::from_millis(20));
+
+            let deadline = Instant::now() + Duration::from_millis(20);
+            stream::once(()).delay(deadline).next().await;
+            assert!(Instant::now() >= deadline);
+        })
+    }
+}
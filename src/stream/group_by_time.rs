@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use super::{interval_at, Interval};
+use crate::time::{Duration, Instant};
+
+pin_project! {
+    /// Group items into fixed buckets of time, labeled with the start of each
+    /// bucket.
+    ///
+    /// This `struct` is created by the [`group_by_time`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`group_by_time`]: crate::stream::StreamExt::group_by_time
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct GroupByTime<S: Stream> {
+        #[pin]
+        stream: S,
+        interval: Interval,
+        bucket: Instant,
+        slot: Vec<S::Item>,
+        done: bool,
+    }
+}
+
+impl<S: Stream> GroupByTime<S> {
+    pub(crate) fn new(stream: S, dur: Duration) -> Self {
+        let start = Instant::now();
+        Self {
+            stream,
+            interval: interval_at(start.saturating_add(dur), dur),
+            bucket: start,
+            slot: Vec::new(),
+            done: false,
+        }
+    }
+}
+
+impl<S: Stream> Stream for GroupByTime<S> {
+    type Item = (Instant, Vec<S::Item>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        // Poll the underlying stream until we get to `Poll::Pending`.
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(value)) => this.slot.push(value),
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    return match this.slot.is_empty() {
+                        true => Poll::Ready(None),
+                        false => Poll::Ready(Some((*this.bucket, mem::take(this.slot)))),
+                    };
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        // Close every bucket which has ended, skipping the empty ones.
+        while let Poll::Ready(Some(tick)) = Pin::new(&mut *this.interval).poll_next(cx) {
+            let bucket = mem::replace(this.bucket, tick);
+            if !this.slot.is_empty() {
+                return Poll::Ready(Some((bucket, mem::take(this.slot))));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<S: Stream> FusedStream for GroupByTime<S> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_core::stream::FusedStream;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn buckets_are_grid_aligned() {
+        crate::utils::block_on(async {
+            let period = Duration::from_millis(20);
+            let mut stream = crate::stream::interval(Duration::from_millis(5))
+                .take(10)
+                .group_by_time(period);
+
+            let mut buckets = vec![];
+            let mut count = 0;
+            while let Some((bucket, items)) = stream.next().await {
+                assert!(!items.is_empty());
+                count += items.len();
+                buckets.push(bucket);
+            }
+            assert!(stream.is_terminated());
+            assert_eq!(count, 10);
+
+            let first = buckets[0];
+            for (prev, next) in buckets.iter().zip(&buckets[1..]) {
+                assert!(next > prev);
+                assert_eq!((*next - first).as_nanos() % period.as_nanos(), 0);
+            }
+        })
+    }
+
+    #[test]
+    fn skips_empty_buckets() {
+        crate::utils::block_on(async {
+            let period = Duration::from_millis(10);
+            let items: Vec<_> = futures_lite::stream::iter(0..2)
+                .chain(futures_lite::stream::once(2).delay(Duration::from_millis(35)))
+                .group_by_time(period)
+                .collect()
+                .await;
+
+            let values: Vec<_> = items.iter().map(|(_, items)| items.clone()).collect();
+            assert_eq!(values, vec![vec![0, 1], vec![2]]);
+            assert_eq!((items[1].0 - items[0].0).as_nanos() % period.as_nanos(), 0);
+            assert!(items[1].0 - items[0].0 >= period * 3);
+        })
+    }
+}
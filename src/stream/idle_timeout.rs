@@ -0,0 +1,106 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::Timer;
+use crate::time::{Duration, Instant};
+
+/// An item yielded by [`IdleTimeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimedItem<T> {
+    /// An item yielded by the underlying stream.
+    Item(T),
+    /// No item was yielded before the deadline, and the underlying stream has
+    /// been idle for the given duration.
+    Idle(Duration),
+}
+
+pin_project! {
+    /// A stream which reports idle periods instead of failing.
+    ///
+    /// This `struct` is created by the [`idle_timeout`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`idle_timeout`]: crate::stream::StreamExt::idle_timeout
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct IdleTimeout<S, D> {
+        #[pin]
+        stream: S,
+        #[pin]
+        deadline: D,
+        last_item: Instant,
+    }
+}
+
+impl<S, D> IdleTimeout<S, D> {
+    pub(crate) fn new(stream: S, deadline: D) -> Self {
+        Self {
+            stream,
+            deadline,
+            last_item: Instant::now(),
+        }
+    }
+}
+
+impl<S: Stream, D: Timer> Stream for IdleTimeout<S, D> {
+    type Item = TimedItem<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        let item = match this.stream.poll_next(cx) {
+            Poll::Ready(Some(v)) => {
+                *this.last_item = Instant::now();
+                TimedItem::Item(v)
+            }
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => match this.deadline.as_mut().poll(cx) {
+                Poll::Ready(_) => TimedItem::Idle(this.last_item.elapsed()),
+                Poll::Pending => return Poll::Pending,
+            },
+        };
+
+        this.deadline.as_mut().reset_timer();
+        Poll::Ready(Some(item))
+    }
+}
+
+impl<S: FusedStream, D: Timer> FusedStream for IdleTimeout<S, D> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use super::TimedItem;
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn reports_idle_periods() {
+        crate::utils::block_on(async {
+            let mut stream = stream::once("meow")
+                .delay(Duration::from_millis(100))
+                .idle_timeout(Duration::from_millis(40));
+
+            let first = match stream.next().await.unwrap() {
+                TimedItem::Idle(dur) => dur,
+                item => panic!("expected an idle period, got {:?}", item),
+            };
+            let second = match stream.next().await.unwrap() {
+                TimedItem::Idle(dur) => dur,
+                item => panic!("expected an idle period, got {:?}", item),
+            };
+            assert!(second > first);
+            assert_eq!(stream.next().await, Some(TimedItem::Item("meow")));
+            assert_eq!(stream.next().await, None);
+        })
+    }
+}
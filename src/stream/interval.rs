@@ -1,54 +1,937 @@
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use async_io::Timer;
-use futures_core::stream::Stream;
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
 
-use crate::time::{Duration, Instant};
+use crate::time::{Clock, Duration, Instant, SystemClock};
+use crate::utils::{jittered, Stopwatch};
 
 /// Creates a new stream that yields at a set interval.
 ///
 /// The stream first yields after `dur`, and continues to yield every
-/// `dur` after that. The stream accounts for time elapsed between calls, and
-/// will adjust accordingly to prevent time skews.
+/// `dur` after that. Ticks are scheduled on a fixed grid of instants spaced
+/// exactly `dur` apart, so the time spent by the consumer between calls does
+/// not cause the ticks to drift.
+///
+/// If the consumer falls behind and misses ticks, they are yielded right away
+/// until the stream has caught up with the grid. This can be changed using
+/// [`Interval::set_missed_tick_behavior`].
 ///
 /// Each interval may be slightly longer than the specified duration, but never
 /// less.
 ///
+/// Each item is the [`Instant`] the tick was scheduled for. Comparing it to
+/// [`Instant::now`] tells how late the tick was observed.
+///
 /// Note that intervals are not intended for high resolution timers, but rather
 /// they will likely fire some granularity after the exact instant that they're
 /// otherwise indicated to fire at.
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::stream;
+/// use futures_time::time::{Duration, Instant};
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let mut interval = stream::interval(Duration::from_millis(10));
+///         let scheduled = interval.next().await.unwrap();
+///         let lateness = Instant::now() - scheduled;
+///         assert!(lateness < Duration::from_secs(1));
+///     });
+/// }
+/// ```
 pub fn interval(dur: Duration) -> Interval {
-    Interval {
-        timer: Timer::after(dur.into()),
-        interval: dur,
+    Interval::with_clock(dur, SystemClock)
+}
+
+/// Creates a new stream that yields at a set interval, starting at `start`.
+///
+/// The stream first yields at `start`, and continues to yield every `period`
+/// after that, on a fixed grid of instants. Otherwise this behaves the same as
+/// [`interval`]. `start` can be either a [`time::Instant`](Instant) or a
+/// `std::time::Instant`.
+///
+/// Passing a stream created with this function to [`StreamExt::sample`],
+/// [`StreamExt::throttle`] or [`StreamExt::buffer`] aligns their windows to
+/// the same grid. By picking a `start` which lies on a wall clock boundary,
+/// such as a whole second, each window corresponds to a fixed bucket of
+/// wall clock time, rather than starting whenever the stream was created.
+///
+/// [`StreamExt::sample`]: crate::stream::StreamExt::sample
+/// [`StreamExt::throttle`]: crate::stream::StreamExt::throttle
+/// [`StreamExt::buffer`]: crate::stream::StreamExt::buffer
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::prelude::*;
+/// use futures_time::stream;
+/// use futures_time::time::{Duration, Instant};
+/// use std::time::{SystemTime, UNIX_EPOCH};
+///
+/// /// Returns the instant of the next wall clock multiple of `period`.
+/// fn next_aligned_instant(period: Duration) -> Instant {
+///     let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+///     let rem = since_epoch.as_nanos() % period.as_nanos();
+///     let until = period.as_nanos() - rem;
+///     Instant::now() + Duration::new(0, until as u32)
+/// }
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         // Sample on every tenth of a second of the wall clock.
+///         let period = Duration::from_millis(100);
+///         let buckets = stream::interval_at(next_aligned_instant(period), period);
+///         let samples: Vec<_> = stream::interval(Duration::from_millis(30))
+///             .take(10)
+///             .sample(buckets)
+///             .collect()
+///             .await;
+///         assert!(!samples.is_empty());
+///     });
+/// }
+/// ```
+pub fn interval_at<I>(start: I, period: Duration) -> Interval
+where
+    I: Into<Instant>,
+{
+    Interval::with_clock_at(start, period, SystemClock)
+}
+
+/// How an [`Interval`] behaves when ticks are missed.
+///
+/// A tick is missed when the consumer of the interval does not poll it until
+/// after the instant the next tick was scheduled for. These behaviors match
+/// those of the same name in Tokio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MissedTickBehavior {
+    /// Yield every missed tick right away, until the interval has caught up.
+    ///
+    /// This is the default.
+    #[default]
+    Burst,
+    /// Yield one tick right away, and skip the other missed ticks. The next
+    /// tick after that is scheduled on the original grid.
+    Skip,
+    /// Yield one tick right away, and skip the other missed ticks. The next
+    /// tick after that is scheduled a full period after the late tick was
+    /// observed, which moves the grid back by the time the consumer fell
+    /// behind.
+    Delay,
+}
+
+pin_project! {
+    /// A stream representing notifications at fixed interval
+    ///
+    /// This stream is created by the [`interval`] function. See its
+    /// documentation for more.
+    ///
+    /// [`interval`]: fn.interval.html
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    #[derive(Debug)]
+    pub struct Interval<C = SystemClock>
+    where
+        C: Clock,
+    {
+        clock: C,
+        timer: C::Timer,
+        interval: Duration,
+        next: Instant,
+        missed_tick_behavior: MissedTickBehavior,
+        stopwatch: Stopwatch,
     }
 }
 
-/// A stream representing notifications at fixed interval
+impl<C: Clock> Interval<C> {
+    /// Creates a new stream that yields at a set interval, using the given
+    /// clock to create the underlying timer.
+    pub fn with_clock(dur: Duration, clock: C) -> Self {
+        Self::with_clock_at(clock.now().saturating_add(dur), dur, clock)
+    }
+
+    /// Creates a new stream that yields at a set interval starting at `start`,
+    /// using the given clock to create the underlying timer.
+    pub fn with_clock_at<I>(start: I, dur: Duration, clock: C) -> Self
+    where
+        I: Into<Instant>,
+    {
+        let start = start.into();
+        Self {
+            timer: clock.timer_at(start),
+            clock,
+            interval: dur,
+            next: start,
+            missed_tick_behavior: MissedTickBehavior::default(),
+            stopwatch: Stopwatch::start(),
+        }
+    }
+
+    /// Waits until the next tick of the interval.
+    ///
+    /// The returned future resolves to the [`Instant`] the tick was scheduled
+    /// for. This is the same as calling `next` on the stream, without needing a
+    /// `StreamExt` trait in scope. Ticks are shared with the stream: both can
+    /// be used on the same interval, one after the other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::stream;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let mut interval = stream::interval(Duration::from_millis(10));
+    ///         let first = interval.tick().await;
+    ///         let second = interval.tick().await;
+    ///         assert_eq!(second - first, Duration::from_millis(10));
+    ///     });
+    /// }
+    /// ```
+    pub fn tick(&mut self) -> Tick<'_, C> {
+        Tick { interval: self }
+    }
+
+    /// Yield the first tick right away, rather than one period from now.
+    ///
+    /// The first tick is scheduled for the instant this method is called, and
+    /// the ticks after it follow one period apart from there. This is the same
+    /// as creating the interval with [`interval_at`] and the current instant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::stream;
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let start = Instant::now();
+    ///         let mut interval = stream::interval(Duration::from_secs(1)).immediate();
+    ///         interval.tick().await;
+    ///         assert!(start.elapsed() < Duration::from_secs(1));
+    ///     });
+    /// }
+    /// ```
+    pub fn immediate(mut self) -> Self {
+        self.next = self.clock.now();
+        self.clock.set_at(&mut self.timer, self.next);
+        self
+    }
+
+    /// Restart the interval, so the next tick is one period from now.
+    ///
+    /// The ticks after it follow one period apart from there.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::stream;
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// let mut interval = stream::interval(Duration::from_secs(1));
+    /// let before = interval.next_tick();
+    /// std::thread::sleep(std::time::Duration::from_millis(10));
+    /// interval.reset();
+    /// assert!(interval.next_tick() > before);
+    /// ```
+    pub fn reset(&mut self) {
+        self.next = self.clock.now().saturating_add(self.interval);
+        self.clock.set_at(&mut self.timer, self.next);
+    }
+
+    /// Returns the instant the next tick is scheduled for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::stream;
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now() + Duration::from_secs(1);
+    /// let interval = stream::interval_at(start, Duration::from_secs(1));
+    /// assert_eq!(interval.next_tick(), start);
+    /// ```
+    pub fn next_tick(&self) -> Instant {
+        self.next
+    }
+
+    /// Returns how this interval behaves when ticks are missed.
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    /// Sets how this interval behaves when ticks are missed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::stream::{self, MissedTickBehavior};
+    /// use futures_time::time::Duration;
+    ///
+    /// let mut interval = stream::interval(Duration::from_secs(1));
+    /// interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    /// assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Skip);
+    /// ```
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+}
+
+/// Returns the first tick on the grid of `period`-spaced ticks starting at
+/// `tick` which lies after `now`.
+fn next_aligned(tick: Instant, now: Instant, period: Duration) -> Instant {
+    if period.is_zero() {
+        return now;
+    }
+    let period_nanos = period.as_nanos();
+    let behind = (now - tick).as_nanos() % period_nanos;
+    let rem = period_nanos - behind;
+    now.saturating_add(Duration::new(
+        (rem / 1_000_000_000) as u64,
+        (rem % 1_000_000_000) as u32,
+    ))
+}
+
+impl<C: Clock> Stream for Interval<C> {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if Pin::new(&mut *this.timer).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        trace!(
+            interval = ?this.interval,
+            elapsed = ?this.stopwatch.elapsed(),
+            "interval tick"
+        );
+        this.stopwatch.restart();
+
+        let tick = *this.next;
+        let next = tick.saturating_add(*this.interval);
+        let now = this.clock.now();
+        *this.next = match this.missed_tick_behavior {
+            _ if next > now => next,
+            MissedTickBehavior::Burst => next,
+            MissedTickBehavior::Skip => next_aligned(tick, now, *this.interval),
+            MissedTickBehavior::Delay => now.saturating_add(*this.interval),
+        };
+        this.clock.set_at(this.timer, *this.next);
+        Poll::Ready(Some(tick))
+    }
+}
+
+impl<C: Clock> FusedStream for Interval<C> {
+    /// An interval never completes, so this always returns `false`.
+    fn is_terminated(&self) -> bool {
+        false
+    }
+}
+
+/// A future which resolves at the next tick of an [`Interval`].
 ///
-/// This stream is created by the [`interval`] function. See its
+/// This future is created by the [`tick`] method on [`Interval`]. See its
 /// documentation for more.
 ///
-/// [`interval`]: fn.interval.html
+/// [`tick`]: Interval::tick
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct Tick<'a, C: Clock = SystemClock> {
+    interval: &'a mut Interval<C>,
+}
+
+impl<C: Clock> fmt::Debug for Tick<'_, C>
+where
+    Interval<C>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tick")
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl<C: Clock> Future for Tick<'_, C> {
+    type Output = Instant;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut *self.interval).poll_next(cx) {
+            Poll::Ready(Some(instant)) => Poll::Ready(instant),
+            Poll::Ready(None) => unreachable!("intervals never end"),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Creates a new stream that yields at a set interval, and ends after `count`
+/// ticks.
+///
+/// This behaves like [`interval`], but the stream ends after yielding `count`
+/// times. The timer is released as soon as the last tick has fired, and the
+/// stream reports that it is done through [`FusedStream::is_terminated`].
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::stream;
+/// use futures_time::time::Duration;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let ticks = stream::interval_count(Duration::from_millis(5), 5);
+///         assert_eq!(ticks.count().await, 5);
+///     });
+/// }
+/// ```
+pub fn interval_count(period: Duration, count: usize) -> IntervalCount {
+    IntervalCount::with_clock(period, count, SystemClock)
+}
+
+/// A stream which yields at a fixed interval a set number of times.
+///
+/// This stream is created by the [`interval_count`] function. See its
+/// documentation for more.
 #[must_use = "streams do nothing unless polled or .awaited"]
-#[derive(Debug)]
-pub struct Interval {
-    timer: Timer,
-    interval: Duration,
+pub struct IntervalCount<C: Clock = SystemClock> {
+    interval: Option<Interval<C>>,
+    remaining: usize,
+}
+
+impl<C: Clock> fmt::Debug for IntervalCount<C>
+where
+    Interval<C>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntervalCount")
+            .field("interval", &self.interval)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+impl<C: Clock> IntervalCount<C> {
+    /// Creates a new stream that yields `count` times at a set interval, using
+    /// the given clock to create the underlying timer.
+    pub fn with_clock(period: Duration, count: usize, clock: C) -> Self {
+        Self {
+            interval: match count {
+                0 => None,
+                _ => Some(Interval::with_clock(period, clock)),
+            },
+            remaining: count,
+        }
+    }
+
+    /// Returns the number of ticks left before the stream ends.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<C: Clock> Stream for IntervalCount<C> {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let interval = match &mut this.interval {
+            Some(interval) => interval,
+            None => return Poll::Ready(None),
+        };
+        if this.remaining > 1 {
+            let tick = futures_core::ready!(Pin::new(interval).poll_next(cx));
+            this.remaining -= 1;
+            return Poll::Ready(tick);
+        }
+
+        // Don't arm the timer again for the last tick.
+        futures_core::ready!(Pin::new(&mut interval.timer).poll(cx));
+        let tick = interval.next;
+        this.interval = None;
+        this.remaining = 0;
+        Poll::Ready(Some(tick))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<C: Clock> FusedStream for IntervalCount<C> {
+    fn is_terminated(&self) -> bool {
+        self.interval.is_none()
+    }
+}
+
+/// Creates a new stream that yields at a set interval, with each tick moved by
+/// a random amount of time.
+///
+/// Intervals which start at the same time keep firing together, which can
+/// cause load spikes on shared resources. This spreads them out by firing
+/// each tick somewhere within `[period - jitter, period + jitter)` of its
+/// nominal time. The nominal ticks are spaced exactly `period` apart, so on
+/// average the stream still yields once every `period` and does not drift.
+///
+/// `random` is called once per tick and should return a value uniformly
+/// distributed in `[0, 1)`; this lets the caller pick a source of randomness.
+/// Values outside of that range are clamped.
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::stream;
+/// use futures_time::time::Duration;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let period = Duration::from_millis(10);
+///         let jitter = Duration::from_millis(5);
+///         let mut interval = stream::interval_jittered(period, jitter, || 0.5).take(2);
+///         while let Some(_) = interval.next().await {}
+///     });
+/// }
+/// ```
+pub fn interval_jittered<R>(period: Duration, jitter: Duration, random: R) -> JitteredInterval<R>
+where
+    R: FnMut() -> f64,
+{
+    JitteredInterval::with_clock(period, jitter, random, SystemClock)
+}
+
+pin_project! {
+    /// A stream representing notifications at a jittered interval.
+    ///
+    /// This stream is created by the [`interval_jittered`] function. See its
+    /// documentation for more.
+    ///
+    /// [`interval_jittered`]: fn.interval_jittered.html
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    #[derive(Debug)]
+    pub struct JitteredInterval<R, C = SystemClock>
+    where
+        C: Clock,
+    {
+        clock: C,
+        timer: C::Timer,
+        period: Duration,
+        jitter: Duration,
+        random: R,
+        nominal: Instant,
+    }
 }
 
-impl Stream for Interval {
+impl<R, C> JitteredInterval<R, C>
+where
+    R: FnMut() -> f64,
+    C: Clock,
+{
+    /// Creates a new stream that yields at a jittered interval, using the
+    /// given clock to create the underlying timer.
+    pub fn with_clock(period: Duration, jitter: Duration, mut random: R, clock: C) -> Self {
+        let nominal = clock.now().saturating_add(period);
+        let timer = clock.timer_at(jitter_at(nominal, jitter, random()));
+        Self {
+            clock,
+            timer,
+            period,
+            jitter,
+            random,
+            nominal,
+        }
+    }
+}
+
+/// Offsets `nominal` by a random amount in `[-jitter, jitter)`.
+///
+/// An offset which does not fit saturates: a deadline which would be after
+/// the end of time is clamped to it, and one before the start of the
+/// monotonic clock is left at `nominal`.
+fn jitter_at(nominal: Instant, jitter: Duration, random: f64) -> Instant {
+    let offset = jittered(Duration::ZERO, (*jitter).saturating_mul(2).into(), random);
+    match offset >= jitter {
+        true => nominal.saturating_add(offset - jitter),
+        false => nominal
+            .checked_sub(*(jitter - offset))
+            .map(Instant::from)
+            .unwrap_or(nominal),
+    }
+}
+
+impl<R, C> Stream for JitteredInterval<R, C>
+where
+    R: FnMut() -> f64,
+    C: Clock,
+{
     type Item = Instant;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let instant = match Pin::new(&mut self.timer).poll(cx) {
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let instant = match Pin::new(&mut *this.timer).poll(cx) {
+            Poll::Ready(instant) => instant,
+            Poll::Pending => return Poll::Pending,
+        };
+        *this.nominal = this.nominal.saturating_add(*this.period);
+        let at = jitter_at(*this.nominal, *this.jitter, (this.random)());
+        this.clock.set_at(this.timer, at);
+        Poll::Ready(Some(instant))
+    }
+}
+
+impl<R, C> FusedStream for JitteredInterval<R, C>
+where
+    R: FnMut() -> f64,
+    C: Clock,
+{
+    /// An interval never completes, so this always returns `false`.
+    fn is_terminated(&self) -> bool {
+        false
+    }
+}
+
+/// Creates a new stream that yields after each successive duration.
+///
+/// The stream first yields after the first duration, then after the second
+/// duration, and so on. Each duration is measured from the moment the
+/// previous tick was observed. The stream ends once `durations` is exhausted,
+/// so an empty iterator creates a stream which ends right away.
+///
+/// This makes it possible to drive operators such as
+/// [`StreamExt::throttle`](crate::stream::StreamExt::throttle) with a
+/// non-uniform schedule, such as a backoff.
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::stream;
+/// use futures_time::time::Duration;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let schedule = vec![
+///             Duration::from_millis(1),
+///             Duration::from_millis(2),
+///             Duration::from_millis(4),
+///         ];
+///         let ticks = stream::intervals(schedule).count().await;
+///         assert_eq!(ticks, 3);
+///     });
+/// }
+/// ```
+pub fn intervals<I>(durations: I) -> Intervals<I::IntoIter>
+where
+    I: IntoIterator<Item = Duration>,
+{
+    Intervals::with_clock(durations, SystemClock)
+}
+
+pin_project! {
+    /// A stream which yields after each duration of a schedule.
+    ///
+    /// This stream is created by the [`intervals`] function. See its
+    /// documentation for more.
+    ///
+    /// [`intervals`]: fn.intervals.html
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    #[derive(Debug)]
+    pub struct Intervals<I, C = SystemClock>
+    where
+        C: Clock,
+    {
+        clock: C,
+        timer: Option<C::Timer>,
+        durations: I,
+    }
+}
+
+impl<I, C> Intervals<I, C>
+where
+    I: Iterator<Item = Duration>,
+    C: Clock,
+{
+    /// Creates a new stream that yields after each successive duration, using
+    /// the given clock to create the underlying timer.
+    pub fn with_clock<D>(durations: D, clock: C) -> Self
+    where
+        D: IntoIterator<IntoIter = I>,
+    {
+        let mut durations = durations.into_iter();
+        Self {
+            timer: durations.next().map(|dur| clock.timer_after(dur)),
+            clock,
+            durations,
+        }
+    }
+}
+
+impl<I, C> Stream for Intervals<I, C>
+where
+    I: Iterator<Item = Duration>,
+    C: Clock,
+{
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let timer = match this.timer {
+            Some(timer) => timer,
+            None => return Poll::Ready(None),
+        };
+        let instant = match Pin::new(&mut *timer).poll(cx) {
             Poll::Ready(instant) => instant,
             Poll::Pending => return Poll::Pending,
         };
-        let interval = self.interval;
-        let _ = std::mem::replace(&mut self.timer, Timer::after(interval.into()));
-        Poll::Ready(Some(instant.into()))
+        match this.durations.next() {
+            Some(dur) => this.clock.set_after(timer, dur),
+            None => *this.timer = None,
+        }
+        Poll::Ready(Some(instant))
+    }
+}
+
+impl<I, C> FusedStream for Intervals<I, C>
+where
+    I: Iterator<Item = Duration>,
+    C: Clock,
+{
+    fn is_terminated(&self) -> bool {
+        self.timer.is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn interval_count_ends() {
+        crate::utils::block_on(async {
+            let mut stream = interval_count(Duration::from_millis(1), 3);
+            assert_eq!(stream.size_hint(), (3, Some(3)));
+            let mut ticks = 0;
+            while stream.next().await.is_some() {
+                ticks += 1;
+            }
+            assert_eq!(ticks, 3);
+            assert!(stream.is_terminated());
+            assert!(stream.next().await.is_none());
+
+            let mut empty = interval_count(Duration::from_secs(3600), 0);
+            assert!(empty.is_terminated());
+            assert!(empty.next().await.is_none());
+        })
+    }
+
+    #[test]
+    fn intervals_empty() {
+        crate::utils::block_on(async {
+            let mut stream = intervals(vec![]);
+            assert!(stream.next().await.is_none());
+            assert!(stream.is_terminated());
+        })
+    }
+
+    #[test]
+    fn yields_scheduled_instants() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let period = Duration::from_millis(10);
+            let ticks: Vec<_> = interval(period).take(3).collect().await;
+            assert!(ticks[0] >= start + period);
+            assert!(ticks[1] >= ticks[0] + period);
+            assert!(ticks[2] >= ticks[1] + period);
+            assert!(ticks[2] <= Instant::now());
+        })
+    }
+
+    #[test]
+    fn stays_on_grid_with_slow_consumer() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let period = Duration::from_millis(20);
+            let mut interval = interval(period);
+            let mut ticks = vec![];
+            while let Some(tick) = interval.next().await {
+                ticks.push(tick);
+                if ticks.len() == 5 {
+                    break;
+                }
+                if ticks.len() == 1 {
+                    crate::task::sleep(Duration::from_millis(70)).await;
+                }
+            }
+
+            // Missed ticks are yielded back to back, rather than pushing the
+            // whole schedule back.
+            for pair in ticks.windows(2) {
+                assert_eq!(pair[1] - pair[0], period);
+            }
+            assert!(start.elapsed() < Duration::from_millis(150));
+        })
+    }
+
+    #[test]
+    fn tick_and_stream_share_schedule() {
+        crate::utils::block_on(async {
+            let period = Duration::from_millis(10);
+            let mut interval = interval(period);
+            let first = interval.tick().await;
+            let second = interval.next().await.unwrap();
+            let third = interval.tick().await;
+            assert_eq!(second - first, period);
+            assert_eq!(third - second, period);
+        })
+    }
+
+    #[test]
+    fn next_tick_advances() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let period = Duration::from_millis(5);
+            let mut interval = interval_at(start, period);
+            assert_eq!(interval.next_tick(), start);
+            interval.tick().await;
+            assert_eq!(interval.next_tick(), start + period);
+        })
+    }
+
+    #[test]
+    fn interval_at_std_instant() {
+        crate::utils::block_on(async {
+            let start = std::time::Instant::now() + std::time::Duration::from_millis(5);
+            let period = Duration::from_millis(10);
+            let ticks: Vec<_> = interval_at(start, period).take(2).collect().await;
+            assert_eq!(ticks, vec![start.into(), Instant::from(start) + period]);
+        })
+    }
+
+    #[test]
+    fn immediate_first_tick() {
+        crate::utils::block_on(async {
+            let period = Duration::from_millis(50);
+            let start = Instant::now();
+            let mut interval = interval(period).immediate();
+
+            let first = interval.tick().await;
+            assert!(start.elapsed() < period / 2);
+            let second = interval.tick().await;
+            assert_eq!(second - first, period);
+            assert!(start.elapsed() >= period);
+        })
+    }
+
+    #[test]
+    fn interval_at_start() {
+        crate::utils::block_on(async {
+            let start = Instant::now() + Duration::from_millis(5);
+            let period = Duration::from_millis(20);
+            let ticks: Vec<_> = interval_at(start, period).take(2).collect().await;
+            assert_eq!(ticks, vec![start, start + period]);
+        })
+    }
+
+    #[test]
+    fn burst_missed_ticks() {
+        futures_lite::future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let period = Duration::from_millis(10);
+            let mut interval = Interval::with_clock(period, clock.clone());
+            let start = clock.now();
+
+            // The consumer falls behind by five and a half periods.
+            clock.advance(Duration::from_millis(55));
+            let mut ticks = vec![];
+            while let Some(tick) = futures_lite::future::poll_once(interval.tick()).await {
+                ticks.push(tick);
+            }
+            let expected: Vec<_> = (1..=5).map(|n| start + period * n).collect();
+            assert_eq!(ticks, expected);
+
+            // Once caught up, the interval stays on the original grid.
+            clock.advance(Duration::from_millis(5));
+            assert_eq!(interval.tick().await, start + period * 6);
+        })
+    }
+
+    #[test]
+    fn skip_missed_ticks() {
+        crate::utils::block_on(async {
+            let period = Duration::from_millis(20);
+            let mut interval = interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            let first = interval.next().await.unwrap();
+            crate::task::sleep(Duration::from_millis(50)).await;
+            let late = interval.next().await.unwrap();
+            let next = interval.next().await.unwrap();
+
+            // The late tick is yielded once, after which the interval skips
+            // ahead to the next tick on the original grid.
+            assert_eq!(late - first, period);
+            assert_eq!(next - first, period * 3);
+        })
+    }
+
+    #[test]
+    fn delay_missed_ticks() {
+        crate::utils::block_on(async {
+            let period = Duration::from_millis(20);
+            let mut interval = interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            let first = interval.next().await.unwrap();
+            crate::task::sleep(Duration::from_millis(80)).await;
+            let late = interval.next().await.unwrap();
+            let next = interval.next().await.unwrap();
+            let after = interval.next().await.unwrap();
+
+            // The late tick is yielded once, after which the schedule restarts
+            // a full period after the late tick was observed.
+            assert_eq!(late - first, period);
+            assert!(next - late >= period * 3);
+            assert_eq!(after - next, period);
+        })
+    }
+
+    #[test]
+    fn interval_jittered_does_not_drift() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let period = Duration::from_millis(20);
+            let jitter = Duration::from_millis(10);
+            let mut toggle = false;
+            let random = move || {
+                toggle = !toggle;
+                if toggle { 0.0 } else { 0.99 }
+            };
+            let ticks: Vec<_> = interval_jittered(period, jitter, random)
+                .take(4)
+                .collect()
+                .await;
+            assert!(ticks[0] >= start + Duration::from_millis(10));
+            assert!(ticks[3] >= start + Duration::from_millis(89));
+        })
+    }
+
+    #[test]
+    fn interval_jittered_huge_jitter() {
+        futures_lite::future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let period = Duration::from_millis(10);
+            for random in [0.0, 0.5, 0.99] {
+                let mut interval =
+                    JitteredInterval::with_clock(period, Duration::MAX, move || random, clock.clone());
+                let _ = futures_lite::future::poll_once(interval.next()).await;
+            }
+        })
     }
 }
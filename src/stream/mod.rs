@@ -1,23 +1,70 @@
 //! Composable asynchronous iteration.
 
+mod audit;
 mod buffer;
 mod debounce;
+mod dedup_timeout;
 mod delay;
-mod interval;
+#[cfg(feature = "std")]
+mod idle_timeout;
 mod into_stream;
 mod park;
 mod sample;
+mod scan_timeout;
 mod stream_ext;
+mod take_until;
+mod then_after;
 mod throttle;
 mod timeout;
+mod timeout_graced;
+mod timeout_then;
+mod timeout_total;
+mod window;
+mod with_latest_from;
 
+pub use audit::Audit;
 pub use buffer::Buffer;
 pub use debounce::Debounce;
+pub use dedup_timeout::DedupTimeout;
 pub use delay::Delay;
-pub use interval::{interval, Interval};
+#[cfg(feature = "std")]
+pub use idle_timeout::{IdleTimeout, TimedItem};
 pub use into_stream::IntoStream;
 pub use park::Park;
 pub use sample::Sample;
+pub use scan_timeout::ScanTimeout;
 pub use stream_ext::StreamExt;
+pub use take_until::{TakeUntil, TimeoutOnce};
+pub use then_after::ThenAfter;
 pub use throttle::Throttle;
 pub use timeout::Timeout;
+pub use timeout_graced::TimeoutGraced;
+pub use timeout_then::TimeoutThen;
+pub use timeout_total::TimeoutTotal;
+pub use window::{Window, WindowStream};
+pub use with_latest_from::WithLatestFrom;
+
+cfg_rt! {
+    mod group_by_time;
+    mod interval;
+    mod rate_limit;
+    mod replay;
+    mod timeout_backoff;
+    mod timeout_dynamic;
+
+    pub use group_by_time::GroupByTime;
+    pub use interval::{
+        interval, interval_at, interval_count, interval_jittered, intervals, Interval,
+        IntervalCount, Intervals, JitteredInterval, MissedTickBehavior, Tick,
+    };
+    pub use rate_limit::RateLimit;
+    pub use replay::{replay, Replay};
+    pub use timeout_backoff::TimeoutBackoff;
+    pub use timeout_dynamic::TimeoutDynamic;
+}
+
+// The shared timer of a wheel is polled from its waker, which has to be `Send`.
+#[cfg(any(feature = "async-io", feature = "tokio"))]
+mod timeout_wheel;
+#[cfg(any(feature = "async-io", feature = "tokio"))]
+pub use timeout_wheel::{TimeoutOn, TimeoutWheel};
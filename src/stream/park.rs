@@ -1,8 +1,9 @@
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use core::pin::Pin;
+use core::task::{Context, Poll};
 
-use crate::channel::Parker;
+use crate::channel::{ParkState, Parker};
 
+use futures_core::stream::FusedStream;
 use futures_core::{ready, Stream};
 use pin_project_lite::pin_project;
 
@@ -12,8 +13,8 @@ pin_project! {
     /// This `struct` is created by the [`park`] method on [`StreamExt`]. See its
     /// documentation for more.
     ///
-    /// [`park`]: crate::future::FutureExt::park
-    /// [`StreamExt`]: crate::future::StreamExt
+    /// [`park`]: crate::stream::StreamExt::park
+    /// [`StreamExt`]: crate::stream::StreamExt
     #[must_use = "futures do nothing unless polled or .awaited"]
     pub struct Park<S, I>
     where
@@ -24,23 +25,11 @@ pin_project! {
         stream: S,
         #[pin]
         interval: I,
-        state: State,
+        state: ParkState,
+        completed: bool,
     }
 }
 
-/// The internal state
-#[derive(Debug)]
-enum State {
-    /// Actively polling the future.
-    Active,
-    /// The future has been paused, so we wait for a signal from the channel.
-    Suspended,
-    /// The channel has been dropped, no more need to check it!
-    NoChannel,
-    /// The future has completed.
-    Completed,
-}
-
 impl<S, I> Park<S, I>
 where
     S: Stream,
@@ -50,7 +39,8 @@ where
         Self {
             stream,
             interval,
-            state: State::Suspended,
+            state: ParkState::Suspended,
+            completed: false,
         }
     }
 }
@@ -64,37 +54,67 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        loop {
-            match this.state {
-                State::Suspended => match ready!(this.interval.as_mut().poll_next(cx)) {
-                    Some(Parker::Park) => return Poll::Pending,
-                    Some(Parker::Unpark) => *this.state = State::Active,
-                    None => *this.state = State::NoChannel,
-                },
-                State::Active => {
-                    if let Poll::Ready(Some(Parker::Park)) = this.interval.as_mut().poll_next(cx) {
-                        *this.state = State::Suspended;
-                        return Poll::Pending;
-                    }
-                    match ready!(this.stream.as_mut().poll_next(cx)) {
-                        Some(value) => return Poll::Ready(Some(value)),
-                        None => {
-                            *this.state = State::Completed;
-                            return Poll::Ready(None);
-                        }
-                    }
-                }
-                State::NoChannel => match ready!(this.stream.as_mut().poll_next(cx)) {
-                    Some(value) => return Poll::Ready(Some(value)),
-                    None => {
-                        *this.state = State::Completed;
-                        return Poll::Ready(None);
-                    }
-                },
-                State::Completed => panic!("future polled after completing"),
+
+        assert!(!*this.completed, "stream polled after completion");
+
+        ready!(this.state.poll_active(this.interval.as_mut(), cx));
+        match ready!(this.stream.as_mut().poll_next(cx)) {
+            Some(value) => Poll::Ready(Some(value)),
+            None => {
+                *this.completed = true;
+                Poll::Ready(None)
             }
         }
     }
 }
 
-// NOTE(yosh): we should probably test this, but I'm too tired today lol.
+impl<S, I> FusedStream for Park<S, I>
+where
+    S: Stream,
+    I: Stream<Item = Parker>,
+{
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::channel;
+    use crate::prelude::*;
+    use crate::stream;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn park_and_unpark_interval() {
+        crate::utils::block_on(async {
+            let (unparker, receiver) = channel::parker();
+            let mut s = stream::interval(Duration::from_millis(5)).park(receiver);
+
+            let res = s.next().timeout(Duration::from_millis(30)).await;
+            assert!(res.is_err());
+
+            unparker.unpark();
+            assert!(s.next().await.is_some());
+            assert!(s.next().await.is_some());
+
+            unparker.park();
+            let res = s.next().timeout(Duration::from_millis(30)).await;
+            assert!(res.is_err());
+
+            unparker.unpark();
+            assert!(s.next().await.is_some());
+        })
+    }
+
+    #[test]
+    fn runs_when_channel_dropped() {
+        crate::utils::block_on(async {
+            let (unparker, receiver) = channel::parker();
+            let s = futures_lite::stream::iter(1..4).park(receiver);
+            drop(unparker);
+            assert_eq!(s.collect::<Vec<_>>().await, vec![1, 2, 3]);
+        })
+    }
+}
@@ -0,0 +1,205 @@
+use std::convert::TryFrom;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::time::{Clock, Duration, Instant, SystemClock};
+
+pin_project! {
+    /// Delay items to enforce a sustained rate, using a token bucket.
+    ///
+    /// This `struct` is created by the [`rate_limit`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`rate_limit`]: crate::stream::StreamExt::rate_limit
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct RateLimit<S, C = SystemClock>
+    where
+        C: Clock,
+    {
+        #[pin]
+        stream: S,
+        clock: C,
+        timer: Option<C::Timer>,
+        armed: bool,
+        rate: Duration,
+        burst: usize,
+        tokens: usize,
+        last_refill: Instant,
+        done: bool,
+    }
+}
+
+impl<S> RateLimit<S> {
+    pub(crate) fn new(stream: S, rate: Duration, burst: usize) -> Self {
+        Self::with_clock(stream, rate, burst, SystemClock)
+    }
+}
+
+impl<S, C: Clock> RateLimit<S, C> {
+    /// Rate limits `stream`, using the given clock to refill the bucket and
+    /// to wait for the next token.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `burst` is zero.
+    pub fn with_clock(stream: S, rate: Duration, burst: usize, clock: C) -> Self {
+        assert!(burst > 0, "rate limit burst must be greater than zero");
+        Self {
+            stream,
+            last_refill: clock.now(),
+            clock,
+            timer: None,
+            armed: false,
+            rate,
+            burst,
+            tokens: burst,
+            done: false,
+        }
+    }
+}
+
+/// Add the tokens which have become available since the last refill.
+fn refill(
+    now: Instant,
+    tokens: &mut usize,
+    last_refill: &mut Instant,
+    rate: Duration,
+    burst: usize,
+) {
+    if rate.is_zero() {
+        *tokens = burst;
+        *last_refill = now;
+        return;
+    }
+
+    let elapsed = now.duration_since(*last_refill);
+    let new = elapsed.as_nanos() / rate.as_nanos();
+    match usize::try_from(new) {
+        Ok(new) if *tokens + new < burst => {
+            *tokens += new;
+            // `new` is less than `burst`, so this fits in a `u32` in practice.
+            match u32::try_from(new).ok().and_then(|n| rate.checked_mul(n)) {
+                Some(dur) => *last_refill += dur,
+                None => *last_refill = now,
+            }
+        }
+        _ => {
+            *tokens = burst;
+            *last_refill = now;
+        }
+    }
+}
+
+impl<S: Stream, C: Clock> Stream for RateLimit<S, C> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        assert!(!*this.done, "stream polled after completion");
+
+        loop {
+            if *this.tokens == 0 {
+                let now = this.clock.now();
+                refill(now, this.tokens, this.last_refill, *this.rate, *this.burst);
+            }
+
+            // Wait for the next token, without polling the underlying stream.
+            if *this.tokens == 0 {
+                let at = this.last_refill.saturating_add(*this.rate);
+                let timer = match this.timer {
+                    Some(timer) => {
+                        if !*this.armed {
+                            this.clock.set_at(timer, at);
+                        }
+                        timer
+                    }
+                    None => {
+                        let clock = &*this.clock;
+                        this.timer.get_or_insert_with(|| clock.timer_at(at))
+                    }
+                };
+                *this.armed = true;
+                ready!(Pin::new(timer).poll(cx));
+                *this.armed = false;
+                continue;
+            }
+
+            return match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    *this.tokens -= 1;
+                    Poll::Ready(Some(item))
+                }
+                None => {
+                    *this.done = true;
+                    Poll::Ready(None)
+                }
+            };
+        }
+    }
+}
+
+impl<S: Stream, C: Clock> FusedStream for RateLimit<S, C> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RateLimit;
+    use crate::prelude::*;
+    use crate::time::{Duration, Instant, TestClock};
+    use futures_lite::future;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn delays_after_burst() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let mut stream = stream::iter(0..5).rate_limit(Duration::from_millis(20), 2);
+
+            assert_eq!(stream.next().await, Some(0));
+            assert_eq!(stream.next().await, Some(1));
+            assert!(start.elapsed() < Duration::from_millis(20));
+
+            let rest: Vec<_> = stream.collect().await;
+            assert_eq!(rest, vec![2, 3, 4]);
+            assert!(start.elapsed() >= Duration::from_millis(60));
+        })
+    }
+
+    #[test]
+    fn refills_from_clock() {
+        future::block_on(async {
+            let clock = TestClock::new();
+            let rate = Duration::from_secs(1);
+            let mut stream = RateLimit::with_clock(stream::iter(0..4), rate, 2, clock.clone());
+
+            assert_eq!(stream.next().await, Some(0));
+            assert_eq!(stream.next().await, Some(1));
+            assert_eq!(future::poll_once(stream.next()).await, None);
+            assert_eq!(clock.pending_timers(), 1);
+
+            clock.advance(rate);
+            assert_eq!(future::poll_once(stream.next()).await, Some(Some(2)));
+            assert_eq!(future::poll_once(stream.next()).await, None);
+
+            clock.advance(rate);
+            assert_eq!(future::poll_once(stream.next()).await, Some(Some(3)));
+            assert_eq!(future::poll_once(stream.next()).await, None);
+
+            clock.advance(rate);
+            assert_eq!(future::poll_once(stream.next()).await, Some(None));
+            assert_eq!(clock.pending_timers(), 0);
+        })
+    }
+}
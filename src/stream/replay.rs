@@ -0,0 +1,139 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::Deadline;
+use crate::task::{sleep, Sleep};
+use crate::time::Duration;
+
+use super::IntoStream;
+
+/// Creates a new stream which replays a recording of items with their
+/// original timing.
+///
+/// Each item of `recording` is a pair of the delay since the previous item
+/// and a payload. For every pair the stream sleeps for the delay, then yields
+/// the payload. The delay of an item starts once the previous item has been
+/// yielded, and a delay of zero yields the payload right away.
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::stream;
+/// use futures_time::time::{Duration, Instant};
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let now = Instant::now();
+///         let recording = futures_lite::stream::iter(vec![
+///             (Duration::ZERO, "meow"),
+///             (Duration::from_millis(20), "purr"),
+///         ]);
+///         let items: Vec<_> = stream::replay(recording).collect().await;
+///         assert_eq!(items, vec!["meow", "purr"]);
+///         assert!(now.elapsed() >= Duration::from_millis(20));
+///     });
+/// }
+/// ```
+pub fn replay<I, T>(recording: I) -> Replay<I::IntoStream, T>
+where
+    I: IntoStream<Item = (Duration, T)>,
+{
+    Replay {
+        stream: recording.into_stream(),
+        timer: None,
+        pending: None,
+    }
+}
+
+pin_project! {
+    /// A stream which replays a recording of items with their original timing.
+    ///
+    /// This stream is created by the [`replay`] function. See its
+    /// documentation for more.
+    ///
+    /// [`replay`]: fn.replay.html
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct Replay<S, T> {
+        #[pin]
+        stream: S,
+        timer: Option<Sleep>,
+        pending: Option<T>,
+    }
+}
+
+impl<S, T> Stream for Replay<S, T>
+where
+    S: Stream<Item = (Duration, T)>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if this.pending.is_none() {
+            match ready!(this.stream.poll_next(cx)) {
+                Some((dur, item)) if dur.is_zero() => return Poll::Ready(Some(item)),
+                Some((dur, item)) => {
+                    *this.pending = Some(item);
+                    match this.timer {
+                        Some(timer) => Pin::new(timer).push_deadline(dur),
+                        None => *this.timer = Some(sleep(dur)),
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+
+        // A payload is only pending while its delay is running.
+        let timer = this.timer.as_mut().unwrap();
+        ready!(Pin::new(timer).poll(cx));
+        Poll::Ready(this.pending.take())
+    }
+}
+
+impl<S, T> FusedStream for Replay<S, T>
+where
+    S: FusedStream<Item = (Duration, T)>,
+{
+    fn is_terminated(&self) -> bool {
+        self.pending.is_none() && self.stream.is_terminated()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::time::Instant;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn replays_timing() {
+        crate::utils::block_on(async {
+            let trace = vec![
+                (Duration::from_millis(10), 1),
+                (Duration::ZERO, 2),
+                (Duration::from_millis(30), 3),
+                (Duration::from_millis(20), 4),
+            ];
+            let start = Instant::now();
+            let mut stream = replay(futures_lite::stream::iter(trace.clone()));
+
+            let mut expected = Duration::ZERO;
+            for (dur, payload) in trace {
+                expected += dur;
+                assert_eq!(stream.next().await, Some(payload));
+                let elapsed = start.elapsed();
+                assert!(elapsed >= expected);
+                assert!(elapsed < expected + Duration::from_millis(50));
+            }
+            assert_eq!(stream.next().await, None);
+        })
+    }
+}
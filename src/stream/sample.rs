@@ -1,14 +1,18 @@
 use pin_project_lite::pin_project;
 
-use futures_core::stream::Stream;
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::stream::{FusedStream, Stream};
 
 pin_project! {
     /// Yield the last value received, if any, at each interval.
     ///
-    /// If no value was emitted during the last interval, no value is emitted
-    /// and we skip to the next interval.
+    /// Use [`keep_first`](Sample::keep_first) to yield the first value received
+    /// instead. If no value was emitted during the last interval, no value is emitted
+    /// and we skip to the next interval. When the underlying stream ends, the
+    /// last value which has not been sampled yet is yielded right away. When
+    /// the interval stream ends, the pending value is yielded and the stream
+    /// ends.
     ///
     /// This `struct` is created by the [`sample`] method on [`StreamExt`]. See its
     /// documentation for more.
@@ -24,6 +28,8 @@ pin_project! {
         interval: I,
         state: State,
         slot: Option<S::Item>,
+        bypass: fn(&S::Item) -> bool,
+        keep_first: bool,
     }
 }
 
@@ -34,8 +40,55 @@ impl<S: Stream, I> Sample<S, I> {
             stream,
             interval,
             slot: None,
+            bypass: |_| false,
+            keep_first: false,
         }
     }
+
+    /// Yield items for which `bypass` returns `true` right away, rather than
+    /// sampling them.
+    pub(crate) fn with_bypass(mut self, bypass: fn(&S::Item) -> bool) -> Self {
+        self.bypass = bypass;
+        self
+    }
+
+    /// Yield the first value received during each interval, and drop the rest.
+    ///
+    /// The value is still yielded at the end of the interval. This differs
+    /// from [`Throttle::leading`](crate::stream::Throttle::leading), which
+    /// yields the first value as soon as it arrives.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::iter(0..3)
+    ///             .sample(Duration::from_millis(100))
+    ///             .keep_first()
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![0]);
+    ///     })
+    /// }
+    /// ```
+    pub fn keep_first(mut self) -> Self {
+        self.keep_first = true;
+        self
+    }
+
+    /// Yield the last value received during each interval, and drop the rest.
+    ///
+    /// This is the default.
+    pub fn keep_last(mut self) -> Self {
+        self.keep_first = false;
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -60,8 +113,13 @@ impl<S: Stream, I: Stream> Stream for Sample<S, I> {
                 // Poll the underlying stream until we get to `Poll::Pending`.
                 loop {
                     match this.stream.as_mut().poll_next(cx) {
+                        Poll::Ready(Some(value)) if (this.bypass)(&value) => {
+                            return Poll::Ready(Some(value));
+                        }
                         Poll::Ready(Some(value)) => {
-                            let _ = this.slot.insert(value);
+                            if !*this.keep_first || this.slot.is_none() {
+                                let _ = this.slot.insert(value);
+                            }
                         }
                         Poll::Ready(None) => {
                             *this.state = State::StreamDone;
@@ -71,15 +129,39 @@ impl<S: Stream, I: Stream> Stream for Sample<S, I> {
                     }
                 }
 
+                // Flush the last value if the stream has ended, rather than
+                // waiting for the next interval.
+                if let State::StreamDone = this.state {
+                    return match this.slot.take() {
+                        Some(item) => Poll::Ready(Some(item)),
+                        None => {
+                            *this.state = State::AllDone;
+                            Poll::Ready(None)
+                        }
+                    };
+                }
+
                 // After the stream, always poll the interval timer.
                 match this.interval.as_mut().poll_next(cx) {
-                    Poll::Ready(_) => {
-                        if let State::StreamDone = this.state {
+                    Poll::Ready(Some(_)) => match this.slot.take() {
+                        Some(item) => Poll::Ready(Some(item)),
+                        // Poll again so the interval registers for its next
+                        // tick, or reports that it has ended.
+                        None => {
                             cx.waker().wake_by_ref();
+                            Poll::Pending
                         }
+                    },
+                    // The interval stream has ended, so there are no more
+                    // windows. Flush the pending value and end the stream.
+                    Poll::Ready(None) => {
+                        *this.state = State::StreamDone;
                         match this.slot.take() {
                             Some(item) => Poll::Ready(Some(item)),
-                            None => Poll::Pending,
+                            None => {
+                                *this.state = State::AllDone;
+                                Poll::Ready(None)
+                            }
                         }
                     }
                     Poll::Pending => Poll::Pending,
@@ -98,17 +180,24 @@ impl<S: Stream, I: Stream> Stream for Sample<S, I> {
     }
 }
 
-#[cfg(test)]
+impl<S: Stream, I: Stream> FusedStream for Sample<S, I> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::AllDone)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
 mod test {
     use crate::prelude::*;
     use crate::time::Duration;
+    use futures_core::stream::FusedStream;
     use futures_lite::prelude::*;
 
     #[test]
     fn smoke() {
-        async_io::block_on(async {
+        crate::utils::block_on(async {
             let interval = Duration::from_millis(100);
-            let throttle = Duration::from_millis(200);
+            let throttle = Duration::from_millis(250);
 
             let take = 4;
             let expected = 2;
@@ -123,4 +212,102 @@ mod test {
             assert_eq!(counter, expected);
         })
     }
+
+    #[test]
+    fn emits_last_value() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = futures_lite::stream::iter(0..3)
+                .sample(Duration::from_secs(3600))
+                .collect()
+                .await;
+            assert_eq!(items, vec![2]);
+        })
+    }
+
+    #[test]
+    fn is_terminated() {
+        crate::utils::block_on(async {
+            let mut stream = crate::stream::interval(Duration::from_millis(5))
+                .take(2)
+                .sample(Duration::from_millis(20));
+            assert!(!stream.is_terminated());
+            while stream.next().await.is_some() {}
+            assert!(stream.is_terminated());
+        })
+    }
+
+    #[test]
+    fn forwards_errors() {
+        crate::utils::block_on(async {
+            let items: Vec<Result<u8, &str>> =
+                futures_lite::stream::iter(vec![Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)])
+                    .sample_ok(Duration::from_secs(1))
+                    .collect()
+                    .await;
+            assert_eq!(items, vec![Err("a"), Err("b"), Ok(3)]);
+        })
+    }
+
+    #[test]
+    fn keep_first_or_last() {
+        crate::utils::block_on(async {
+            let window = Duration::from_millis(50);
+            let sample_first: Vec<_> = futures_lite::stream::iter(0..3)
+                .sample(window)
+                .keep_first()
+                .collect()
+                .await;
+            let sample_last: Vec<_> = futures_lite::stream::iter(0..3)
+                .sample(window)
+                .keep_last()
+                .collect()
+                .await;
+            let throttle_first: Vec<_> = futures_lite::stream::iter(0..3)
+                .throttle(window)
+                .leading()
+                .collect()
+                .await;
+            let throttle_last: Vec<_> = futures_lite::stream::iter(0..3)
+                .throttle(window)
+                .trailing()
+                .collect()
+                .await;
+            assert_eq!(sample_first, vec![0]);
+            assert_eq!(sample_last, vec![2]);
+            assert_eq!(throttle_first, vec![0]);
+            assert_eq!(throttle_last, vec![2]);
+
+            // `sample` waits for the end of the interval, even when keeping the
+            // first value, where `throttle` yields it right away.
+            let items = || futures_lite::stream::iter(0..3).chain(futures_lite::stream::pending());
+
+            let start = std::time::Instant::now();
+            let mut stream = items().sample(window).keep_first();
+            assert_eq!(stream.next().await, Some(0));
+            assert!(start.elapsed() >= *window);
+
+            let start = std::time::Instant::now();
+            let mut stream = items().throttle(window).leading();
+            assert_eq!(stream.next().await, Some(0));
+            assert!(start.elapsed() < *window);
+        })
+    }
+
+    #[test]
+    fn interval_end_ends_stream() {
+        crate::utils::block_on(async {
+            let window = Duration::from_millis(50);
+            let mut stream = futures_lite::stream::iter(0..3)
+                .chain(futures_lite::stream::once(3).delay(Duration::from_millis(75)))
+                .chain(futures_lite::stream::pending())
+                .sample(crate::stream::interval(window).take(3));
+
+            let mut items = vec![];
+            while let Some(item) = stream.next().await {
+                items.push(item);
+            }
+            assert_eq!(items, vec![2, 3]);
+            assert!(stream.is_terminated());
+        })
+    }
 }
@@ -0,0 +1,179 @@
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Fold items into an accumulator, yielding a snapshot of it at each
+    /// interval.
+    ///
+    /// This `struct` is created by the [`scan_timeout`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`scan_timeout`]: crate::stream::StreamExt::scan_timeout
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct ScanTimeout<S, I, St, F> {
+        #[pin]
+        stream: S,
+        #[pin]
+        interval: I,
+        state: St,
+        init: St,
+        f: F,
+        reset: bool,
+        dirty: bool,
+        stream_done: bool,
+        done: bool,
+    }
+}
+
+impl<S, I, St: Clone, F> ScanTimeout<S, I, St, F> {
+    pub(crate) fn new(stream: S, init: St, interval: I, f: F) -> Self {
+        Self {
+            stream,
+            interval,
+            state: init.clone(),
+            init,
+            f,
+            reset: false,
+            dirty: false,
+            stream_done: false,
+            done: false,
+        }
+    }
+
+    /// Reset the accumulator to its initial value every time a snapshot is
+    /// yielded.
+    ///
+    /// By default the accumulator is carried forward, so each snapshot covers
+    /// every item seen so far. With this option each snapshot only covers the
+    /// items seen since the previous snapshot.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_time::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let total: usize = stream::interval(Duration::from_millis(5))
+    ///             .take(10)
+    ///             .scan_timeout(0, Duration::from_millis(20), |count, _| *count += 1)
+    ///             .reset_on_emit()
+    ///             .fold(0, |total, count| total + count)
+    ///             .await;
+    ///         assert_eq!(total, 10);
+    ///     })
+    /// }
+    /// ```
+    pub fn reset_on_emit(mut self) -> Self {
+        self.reset = true;
+        self
+    }
+}
+
+/// Take a snapshot of the accumulator, resetting it if requested.
+fn snapshot<St: Clone>(state: &mut St, init: &St, reset: bool) -> St {
+    match reset {
+        true => mem::replace(state, init.clone()),
+        false => state.clone(),
+    }
+}
+
+impl<S, I, St, F> Stream for ScanTimeout<S, I, St, F>
+where
+    S: Stream,
+    I: Stream,
+    St: Clone,
+    F: FnMut(&mut St, S::Item),
+{
+    type Item = St;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        assert!(!*this.done, "stream polled after completion");
+
+        // Accumulate all items which are ready.
+        while !*this.stream_done {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    (this.f)(this.state, item);
+                    *this.dirty = true;
+                }
+                Poll::Ready(None) => *this.stream_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        // Yield a snapshot at every interval.
+        if !*this.stream_done && ready!(this.interval.as_mut().poll_next(cx)).is_none() {
+            *this.stream_done = true;
+        }
+        if !*this.stream_done {
+            *this.dirty = false;
+            return Poll::Ready(Some(snapshot(this.state, this.init, *this.reset)));
+        }
+
+        // Once either stream ends, yield a final snapshot of any items which
+        // were not covered by the last one.
+        match mem::replace(this.dirty, false) {
+            true => Poll::Ready(Some(snapshot(this.state, this.init, *this.reset))),
+            false => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl<S, I, St, F> FusedStream for ScanTimeout<S, I, St, F>
+where
+    S: Stream,
+    I: Stream,
+    St: Clone,
+    F: FnMut(&mut St, S::Item),
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn carries_state_forward() {
+        crate::utils::block_on(async {
+            let snapshots: Vec<_> = crate::stream::interval(Duration::from_millis(5))
+                .take(10)
+                .scan_timeout(0, Duration::from_millis(20), |count, _| *count += 1)
+                .collect()
+                .await;
+            assert!(snapshots.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert_eq!(snapshots.last(), Some(&10));
+        })
+    }
+
+    #[test]
+    fn final_snapshot_on_end() {
+        crate::utils::block_on(async {
+            let snapshots: Vec<_> = futures_lite::stream::iter(1..4)
+                .scan_timeout(0, Duration::from_secs(10), |sum, n| *sum += n)
+                .collect()
+                .await;
+            assert_eq!(snapshots, vec![6]);
+        })
+    }
+}
@@ -3,7 +3,19 @@ use crate::future::{IntoFuture, Timer};
 
 use futures_core::Stream;
 
-use super::{Buffer, Debounce, Delay, IntoStream, Park, Sample, Throttle, Timeout};
+#[cfg(feature = "std")]
+use super::IdleTimeout;
+cfg_rt! {
+    use super::{GroupByTime, RateLimit, TimeoutBackoff, TimeoutDynamic};
+    use crate::time::Duration;
+}
+use super::{
+    Audit, Buffer, Debounce, DedupTimeout, Delay, IntoStream, Park, Sample, ScanTimeout, TakeUntil,
+    ThenAfter, Throttle, Timeout, TimeoutGraced, TimeoutOnce, TimeoutThen, TimeoutTotal, Window,
+    WithLatestFrom,
+};
+#[cfg(any(feature = "async-io", feature = "tokio"))]
+use super::{TimeoutOn, TimeoutWheel};
 
 /// Extend `Stream` with time-based operations.
 pub trait StreamExt: Stream {
@@ -14,6 +26,15 @@ pub trait StreamExt: Stream {
     /// stream as a source. This enables throttling based on alternative event
     /// sources, such as variable-rate timers.
     ///
+    /// When the stream ends, the last item which has not been yielded yet is
+    /// yielded right away, so the final value is never lost. Likewise, when
+    /// the interval stream ends there are no more windows: the pending sample
+    /// is yielded and then the stream ends.
+    ///
+    /// A time-based interval starts when this method is called. To align the
+    /// samples to fixed instants instead, such as every whole second, pass a
+    /// stream created with [`stream::interval_at`](crate::stream::interval_at).
+    ///
     /// See also [`throttle()`] and [`debounce()`].
     ///
     /// [`throttle()`]: StreamExt::throttle
@@ -42,7 +63,7 @@ pub trait StreamExt: Stream {
     ///        let mut counter = 0;
     ///        stream::interval(Duration::from_millis(100))
     ///            .take(4)
-    ///            .sample(Duration::from_millis(200))
+    ///            .sample(Duration::from_millis(250))
     ///            .for_each(|_| counter += 1)
     ///            .await;
     ///
@@ -50,6 +71,7 @@ pub trait StreamExt: Stream {
     ///    })
     /// }
     /// ```
+    #[doc(alias = "last_per")]
     fn sample<I>(self, interval: I) -> Sample<Self, I::IntoStream>
     where
         Self: Sized,
@@ -58,16 +80,59 @@ pub trait StreamExt: Stream {
         Sample::new(self, interval.into_stream())
     }
 
+    /// Sample the `Ok` values of a fallible stream, forwarding every `Err`
+    /// right away.
+    ///
+    /// This works like [`sample()`], except that errors are never held back
+    /// or overwritten by later items: each `Err` is yielded as soon as it is
+    /// received, so sampling cannot hide failures of the underlying stream.
+    ///
+    /// [`sample()`]: `StreamExt::sample`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<Result<u8, &str>> = stream::iter(vec![Ok(1), Err("oops"), Ok(2)])
+    ///             .sample_ok(Duration::from_millis(100))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![Err("oops"), Ok(2)]);
+    ///     })
+    /// }
+    /// ```
+    fn sample_ok<I, T, E>(self, interval: I) -> Sample<Self, I::IntoStream>
+    where
+        Self: Stream<Item = Result<T, E>> + Sized,
+        I: IntoStream,
+    {
+        Sample::new(self, interval.into_stream()).with_bypass(|item| item.is_err())
+    }
+
     /// Group items into vectors which are yielded at every interval.
     ///
     /// In addition to using a time source as a deadline, any stream can be used as a
     /// deadline too. This enables more interesting buffer strategies to be
     /// built on top of this primitive.
     ///
+    /// To group items into fixed buckets of wall clock time, such as one
+    /// vector per whole second, pass a stream created with
+    /// [`stream::interval_at`](crate::stream::interval_at).
+    ///
+    /// If the interval stream ends before the underlying stream, any items
+    /// buffered so far are flushed once more. After that every item is yielded
+    /// right away, in a `Vec` of its own.
+    ///
     /// # Future Improvements
     ///
     /// - Lending iterators would allow for internal reusing of the buffer.
-    /// Though different from `Iterator::windows`, it could be more efficient.
+    ///   Though different from `Iterator::windows`, it could be more efficient.
     /// - Contexts/capabilities would enable custom allocators to be used.
     ///
     /// # Example
@@ -99,6 +164,156 @@ pub trait StreamExt: Stream {
         Buffer::new(self, interval.into_stream())
     }
 
+    /// Split the stream into consecutive windows, and yield each window as a
+    /// stream of its own.
+    ///
+    /// A new window is opened every time `interval` yields, and the previous
+    /// window is closed at the same time. Unlike [`buffer()`], which collects
+    /// the items of each interval into a `Vec`, the items of a window can be
+    /// processed as soon as they arrive. The first window opens right away.
+    ///
+    /// Windows never overlap: every item belongs to exactly one window, and a
+    /// window is yielded even if no items arrive while it is open. Items are
+    /// queued inside their window until they are read, so windows may be
+    /// consumed one after another, or concurrently. Both the windows and the
+    /// stream of windows drive the underlying stream when polled. If the
+    /// interval stream ends, the last window stays open until the underlying
+    /// stream ends.
+    ///
+    /// The windows share the underlying stream through a reference-counted
+    /// pointer, so they are not `Send`.
+    ///
+    /// [`buffer()`]: `StreamExt::buffer`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_time::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let mut windows = stream::interval(Duration::from_millis(5))
+    ///             .take(10)
+    ///             .window(Duration::from_millis(20));
+    ///
+    ///         let mut counter = 0;
+    ///         while let Some(mut window) = windows.next().await {
+    ///             while let Some(_) = window.next().await {
+    ///                 counter += 1;
+    ///             }
+    ///         }
+    ///         assert_eq!(counter, 10);
+    ///     })
+    /// }
+    /// ```
+    fn window<I>(self, interval: I) -> Window<Self, I::IntoStream>
+    where
+        Self: Sized,
+        I: IntoStream,
+    {
+        Window::new(self, interval.into_stream())
+    }
+
+    cfg_rt! {
+        /// Group items into fixed buckets of time, and yield each bucket together
+        /// with the instant it started at.
+        ///
+        /// The buckets lie on a grid which starts when this method is called, and
+        /// every bucket is `dur` long. This is like [`buffer()`] with a duration,
+        /// but each `Vec` is labeled with the start of its bucket, which makes it
+        /// possible to merge the buckets of several streams by key. Buckets in
+        /// which no items arrived are skipped, so no empty `Vec`s are yielded. When
+        /// the stream ends, the items of the current bucket are yielded right
+        /// away.
+        ///
+        /// [`buffer()`]: `StreamExt::buffer`
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use futures_lite::prelude::*;
+        /// use futures_time::prelude::*;
+        /// use futures_time::time::{Duration, Instant};
+        /// use futures_time::stream;
+        ///
+        /// fn main() {
+        ///     async_io::block_on(async {
+        ///         let start = Instant::now();
+        ///         let buckets: Vec<_> = stream::interval(Duration::from_millis(5))
+        ///             .take(10)
+        ///             .group_by_time(Duration::from_millis(20))
+        ///             .collect()
+        ///             .await;
+        ///
+        ///         let (first, _) = buckets[0];
+        ///         assert!(first >= start);
+        ///         assert_eq!(buckets.iter().map(|(_, items)| items.len()).sum::<usize>(), 10);
+        ///     })
+        /// }
+        /// ```
+        fn group_by_time(self, dur: Duration) -> GroupByTime<Self>
+        where
+            Self: Sized,
+        {
+            GroupByTime::new(self, dur)
+        }
+    }
+
+    /// Fold items into an accumulator, and yield a snapshot of it at every
+    /// interval.
+    ///
+    /// `f` is called with the accumulator and every item the stream yields,
+    /// starting from `init`. Every time `interval` yields, a clone of the
+    /// accumulator is yielded. This differs from [`buffer()`], which yields the
+    /// raw items, and from [`sample()`], which yields the last raw item: this
+    /// yields a value derived from all items, such as a running average.
+    ///
+    /// The accumulator is carried forward across snapshots by default, so a
+    /// snapshot is yielded at every interval even if no new items arrived.
+    /// Use [`ScanTimeout::reset_on_emit`] to start over from `init` after each
+    /// snapshot instead. When either stream ends, a final snapshot is yielded
+    /// if any items arrived since the last one.
+    ///
+    /// [`buffer()`]: `StreamExt::buffer`
+    /// [`sample()`]: `StreamExt::sample`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_time::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let last = stream::interval(Duration::from_millis(5))
+    ///             .take(10)
+    ///             .scan_timeout(0, Duration::from_millis(20), |count, _| *count += 1)
+    ///             .last()
+    ///             .await;
+    ///         assert_eq!(last, Some(10));
+    ///     })
+    /// }
+    /// ```
+    fn scan_timeout<St, I, F>(
+        self,
+        init: St,
+        interval: I,
+        f: F,
+    ) -> ScanTimeout<Self, I::IntoStream, St, F>
+    where
+        Self: Sized,
+        I: IntoStream,
+        St: Clone,
+        F: FnMut(&mut St, Self::Item),
+    {
+        ScanTimeout::new(self, init, interval.into_stream(), f)
+    }
+
     /// Yield the last item received at the end of a window which resets with
     /// each item received.
     ///
@@ -111,6 +326,15 @@ pub trait StreamExt: Stream {
     /// where performing that same action on _every_ event might not be
     /// economical.
     ///
+    /// The window is usually a [`Duration`](crate::time::Duration), but any
+    /// future implementing [`Timer`] can be used. The window is moved forward
+    /// with [`Timer::reset_timer`] every time an item is received, which allows
+    /// custom timers to vary the length of each window.
+    ///
+    /// By default the last item of each burst is yielded. Use
+    /// [`Debounce::leading`] to yield the first item of each burst right away
+    /// instead, or [`Debounce::both`] to yield both.
+    ///
     /// See also [`sample()`] and [`throttle()`].
     ///
     /// [`sample()`]: `StreamExt::sample`
@@ -153,6 +377,14 @@ pub trait StreamExt: Stream {
     /// deadline too. When used in combination with a multi-consumer channel,
     /// this method can be used to synchronize the start of multiple streams and futures.
     ///
+    /// As with [`FutureExt::delay`], a [`Duration`] delays by that amount of
+    /// time counted from when this method is called, while an [`Instant`]
+    /// delays until that point in time.
+    ///
+    /// [`FutureExt::delay`]: crate::future::FutureExt::delay
+    /// [`Duration`]: crate::time::Duration
+    /// [`Instant`]: crate::time::Instant
+    ///
     /// # Example
     ///
     /// ```
@@ -166,7 +398,7 @@ pub trait StreamExt: Stream {
     ///         let now = Instant::now();
     ///         let delay = Duration::from_millis(100);
     ///         let _ = stream::once("meow").delay(delay).next().await;
-    ///         assert!(now.elapsed() >= *delay);
+    ///         assert!(now.elapsed() >= delay);
     ///     });
     /// }
     /// ```
@@ -178,12 +410,78 @@ pub trait StreamExt: Stream {
         Delay::new(self, deadline.into_future())
     }
 
+    /// Yield the items of this stream, then wait for a deadline once it has
+    /// ended, then yield the items of `next`.
+    ///
+    /// This works like chaining two streams, with a pause at the junction.
+    /// Unlike [`delay`](StreamExt::delay), which postpones the start of a
+    /// stream, the deadline is only created once this stream has ended, so a
+    /// [`Duration`] is counted from then. If this stream never ends, `next` is
+    /// never polled.
+    ///
+    /// [`Duration`]: crate::time::Duration
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::{Instant, Duration};
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let now = Instant::now();
+    ///         let cooldown = Duration::from_millis(100);
+    ///         let items: Vec<_> = stream::iter(0..2)
+    ///             .then_after(cooldown, stream::iter(2..4))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![0, 1, 2, 3]);
+    ///         assert!(now.elapsed() >= cooldown);
+    ///     });
+    /// }
+    /// ```
+    fn then_after<D, U>(self, dur: D, next: U) -> ThenAfter<Self, D, U>
+    where
+        Self: Sized,
+        D: IntoFuture,
+        U: Stream<Item = Self::Item>,
+    {
+        ThenAfter::new(self, dur, next)
+    }
+
     /// Suspend or resume execution of a stream.
     ///
     /// When this method is called the execution of the stream will be put into
     /// a suspended state until the channel returns `Parker::Unpark` or the
     /// channel's senders are dropped. The underlying stream will not be polled
     /// while the it is paused.
+    ///
+    /// Use a [`parker`](crate::channel::parker) to control the stream: its
+    /// [`Unparker`](crate::channel::Unparker) suspends and resumes the stream
+    /// any number of times. Once every `Unparker` has been dropped the stream
+    /// runs to completion uninterrupted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::channel;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let (unparker, receiver) = channel::parker();
+    ///         let mut s = stream::iter(1..3).park(receiver);
+    ///         unparker.unpark();
+    ///         assert_eq!(s.next().await, Some(1));
+    ///         drop(unparker);
+    ///         assert_eq!(s.next().await, Some(2));
+    ///     });
+    /// }
+    /// ```
     fn park<I>(self, interval: I) -> Park<Self, I::IntoStream>
     where
         Self: Sized,
@@ -198,6 +496,24 @@ pub trait StreamExt: Stream {
     /// stream as a source. This enables throttling based on alternative event
     /// sources, such as variable-rate timers.
     ///
+    /// By default the first item of each interval is yielded. The first
+    /// interval starts when this method is called, so the very first item is
+    /// yielded as soon as it arrives; use [`Throttle::suppress_first`] to drop
+    /// items until the first interval has ended instead. Use
+    /// [`Throttle::trailing`] with a time-based interval to yield the last
+    /// item of each burst instead, one interval after the first item of the
+    /// burst arrived. Unlike [`sample()`], whose windows form a fixed grid, a
+    /// trailing window starts when an item arrives. To wait for a quiet period
+    /// after the last item instead, use [`debounce()`], whose window is reset
+    /// by every item.
+    ///
+    /// When the interval stream ends there are no more windows, so the stream
+    /// ends too.
+    ///
+    /// To align the intervals to fixed instants rather than to when this
+    /// method is called, pass a stream created with
+    /// [`stream::interval_at`](crate::stream::interval_at).
+    ///
     /// See also [`sample()`] and [`debounce()`].
     ///
     /// [`sample()`]: `StreamExt::sample`
@@ -213,6 +529,10 @@ pub trait StreamExt: Stream {
     /// lead to unintended data loss when used to discard _unique_ items, such
     /// as network request.
     ///
+    /// To slow down the underlying stream instead of discarding its items, use
+    /// [`Throttle::backpressure`]. The underlying stream is then not polled
+    /// again until the next interval.
+    ///
     /// # Examples
     ///
     /// ```
@@ -234,6 +554,7 @@ pub trait StreamExt: Stream {
     ///     })
     /// }
     /// ```
+    #[doc(alias = "first_per")]
     fn throttle<I>(self, interval: I) -> Throttle<Self, I::IntoStream>
     where
         Self: Sized,
@@ -242,6 +563,87 @@ pub trait StreamExt: Stream {
         Throttle::new(self, interval.into_stream())
     }
 
+    /// Yield up to a variable number of items per window, with the budget of
+    /// each window read from a control stream.
+    ///
+    /// This works like [`throttle()`], except that every item of `control`
+    /// both starts a new window and sets how many items may be yielded during
+    /// it. The first window, before `control` has yielded anything, allows a
+    /// single item. This enables adaptive rate control driven by an external
+    /// feedback signal, such as the free capacity of a downstream queue.
+    ///
+    /// The budget applies when yielding the first items of each window, and
+    /// when applying [`Throttle::backpressure`]. When the control stream
+    /// ends, the throttled stream ends too.
+    ///
+    /// [`throttle()`]: `StreamExt::throttle`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::stream;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         // Allow three items per 10ms window.
+    ///         let control = stream::interval(Duration::from_millis(10)).map(|_| 3);
+    ///         let items: Vec<_> = futures_lite::stream::iter(0..)
+    ///             .throttle_with(control)
+    ///             .backpressure()
+    ///             .take(7)
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![0, 1, 2, 3, 4, 5, 6]);
+    ///     })
+    /// }
+    /// ```
+    fn throttle_with<I>(self, control: I) -> Throttle<Self, I::IntoStream>
+    where
+        Self: Sized,
+        I: IntoStream<Item = usize>,
+    {
+        Throttle::with_window(self, control.into_stream(), |budget| *budget)
+    }
+
+    /// Throttle the `Ok` values of a fallible stream, forwarding every `Err`
+    /// right away.
+    ///
+    /// This works like [`throttle()`], except that errors are never dropped:
+    /// each `Err` is yielded as soon as it is received, and does not count
+    /// against the items allowed per interval. Throttling then cannot hide
+    /// failures of the underlying stream.
+    ///
+    /// [`throttle()`]: `StreamExt::throttle`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<Result<u8, &str>> = stream::iter(vec![Ok(1), Ok(2), Err("oops"), Ok(3)])
+    ///             .throttle_ok(Duration::from_millis(100))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![Ok(1), Err("oops")]);
+    ///     })
+    /// }
+    /// ```
+    fn throttle_ok<I, T, E>(self, interval: I) -> Throttle<Self, I::IntoStream>
+    where
+        Self: Stream<Item = Result<T, E>> + Sized,
+        I: IntoStream,
+    {
+        Throttle::new(self, interval.into_stream()).with_bypass(|item| item.is_err())
+    }
+
     /// Return an error if a stream does not yield an item within a given time
     /// span.
     ///
@@ -250,8 +652,20 @@ pub trait StreamExt: Stream {
     /// combination with channels, as it allows (long-lived) streams to be
     /// cancelled based on some external event.
     ///
-    /// When a timeout is returned, the stream will be dropped and destructors
-    /// will be run.
+    /// A timeout does not end the stream, and no data is lost: when the
+    /// deadline passes an error is yielded and the deadline is reset, but the
+    /// underlying stream is kept and polled again on the next call. An item
+    /// which was still being computed when the timeout fired is yielded by a
+    /// later call once it is ready. Items are always yielded in the order the
+    /// underlying stream produces them; an error only marks that the deadline
+    /// passed before the next item arrived. To stop at the first timeout, end
+    /// the stream when the first error is yielded.
+    ///
+    /// The deadline of the first item is reset when the stream is first
+    /// polled, so time spent before that does not count against it.
+    ///
+    /// The error is a [`TimeoutError`](crate::future::TimeoutError), which converts into an
+    /// [`io::Error`](std::io::Error) of kind `TimedOut` when the `std` feature is enabled.
     ///
     /// # Example
     ///
@@ -269,7 +683,8 @@ pub trait StreamExt: Stream {
     ///             .timeout(Duration::from_millis(50)) // shorter timeout
     ///             .next()
     ///             .await;
-    ///         assert_eq!(res.unwrap().unwrap_err().kind(), io::ErrorKind::TimedOut); // error
+    ///         let err = io::Error::from(res.unwrap().unwrap_err());
+    ///         assert_eq!(err.kind(), io::ErrorKind::TimedOut); // error
     ///
     ///         let res = stream::once("meow")
     ///             .delay(Duration::from_millis(50))    // shorter delay
@@ -288,6 +703,635 @@ pub trait StreamExt: Stream {
     {
         Timeout::new(self, deadline.into_future())
     }
+
+    /// Return an error if the stream does not yield an item within a given
+    /// time span, using a shared [`TimeoutWheel`] as the timer.
+    ///
+    /// This works like [`timeout()`], except that the deadline is tracked by
+    /// the wheel at its resolution, rather than by a timer of its own. This
+    /// is meant for running many timed streams at once, such as one per
+    /// connection: an item only moves the deadline in memory, and all the
+    /// streams on a wheel share a single reactor timer. In exchange a timeout
+    /// may fire up to one resolution of the wheel late. See [`TimeoutWheel`]
+    /// for more.
+    ///
+    /// [`timeout()`]: StreamExt::timeout
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::stream::{self, TimeoutWheel};
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let wheel = TimeoutWheel::new(Duration::from_millis(10));
+    ///         let items: Vec<_> = stream::interval(Duration::from_millis(20))
+    ///             .take(3)
+    ///             .timeout_on(&wheel, Duration::from_millis(100))
+    ///             .collect()
+    ///             .await;
+    ///         assert!(items.iter().all(|item| item.is_ok()));
+    ///     });
+    /// }
+    /// ```
+    #[cfg(any(feature = "async-io", feature = "tokio"))]
+    fn timeout_on(self, wheel: &TimeoutWheel, dur: Duration) -> TimeoutOn<Self>
+    where
+        Self: Sized,
+    {
+        TimeoutOn::new(self, wheel, dur)
+    }
+
+    /// Call a closure whenever a stream does not yield an item within a given
+    /// time span, and let it decide how to continue.
+    ///
+    /// Every time the deadline passes before the next item, `on_timeout` is
+    /// called and the deadline is reset. Returning
+    /// `ControlFlow::Continue(Some(item))` yields `item`, returning
+    /// `ControlFlow::Continue(None)` keeps waiting without yielding anything,
+    /// and returning `ControlFlow::Break(())` ends the stream. The deadline is
+    /// also reset after every item of the underlying stream, and the deadline
+    /// of the first item starts when the stream is first polled.
+    ///
+    /// Other timeout behaviors can be expressed with this method:
+    ///
+    /// - [`timeout()`] maps the items to `Ok`, and returns
+    ///   `Continue(Some(Err(..)))` on every lapse.
+    /// - [`idle_timeout()`] wraps the items in [`TimedItem::Item`], and returns
+    ///   `Continue(Some(TimedItem::Idle(..)))` on every lapse.
+    /// - A fatal timeout returns `Break(())` on the first lapse.
+    /// - A heartbeat returns `Continue(Some(..))` with a placeholder item.
+    ///
+    /// [`timeout()`]: `StreamExt::timeout`
+    /// [`idle_timeout()`]: `StreamExt::idle_timeout`
+    /// [`TimedItem::Item`]: crate::stream::TimedItem::Item
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    /// use std::ops::ControlFlow;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         // Send up to two heartbeats while idle, then give up.
+    ///         let mut heartbeats = 0;
+    ///         let items: Vec<_> = stream::once("data")
+    ///             .chain(stream::pending())
+    ///             .timeout_then(Duration::from_millis(10), || {
+    ///                 heartbeats += 1;
+    ///                 match heartbeats {
+    ///                     1..=2 => ControlFlow::Continue(Some("heartbeat")),
+    ///                     _ => ControlFlow::Break(()),
+    ///                 }
+    ///             })
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec!["data", "heartbeat", "heartbeat"]);
+    ///     });
+    /// }
+    /// ```
+    fn timeout_then<D, F>(self, deadline: D, on_timeout: F) -> TimeoutThen<Self, D::IntoFuture, F>
+    where
+        Self: Sized,
+        D: IntoFuture,
+        D::IntoFuture: Timer,
+        F: FnMut() -> core::ops::ControlFlow<(), Option<Self::Item>>,
+    {
+        TimeoutThen::new(self, deadline.into_future(), on_timeout)
+    }
+
+    cfg_rt! {
+        /// Return an error if a stream does not yield an item within a time span
+        /// which grows while the stream stays idle.
+        ///
+        /// This is like [`timeout`](StreamExt::timeout), but every timeout without
+        /// an item in between multiplies the time span by `factor`, up to `max`.
+        /// When an item arrives the time span goes back to `base`. This is useful
+        /// for polling sources which get quieter over time, where reporting every
+        /// short idle period would be noise. The time span of the first item
+        /// starts when the stream is first polled.
+        ///
+        /// As with `timeout`, a timeout does not end the stream.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `factor` is less than `1.0`.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use futures_lite::prelude::*;
+        /// use futures_time::prelude::*;
+        /// use futures_time::time::Duration;
+        /// use futures_lite::stream;
+        ///
+        /// fn main() {
+        ///     async_io::block_on(async {
+        ///         let base = Duration::from_millis(10);
+        ///         let max = Duration::from_millis(40);
+        ///         let mut s = stream::pending::<()>().timeout_backoff(base, max, 2.0);
+        ///         for expected in [10, 20, 40, 40] {
+        ///             let err = s.next().await.unwrap().unwrap_err();
+        ///             assert_eq!(err.configured(), Some(Duration::from_millis(expected)));
+        ///         }
+        ///     });
+        /// }
+        /// ```
+        fn timeout_backoff(self, base: Duration, max: Duration, factor: f64) -> TimeoutBackoff<Self>
+        where
+            Self: Sized,
+        {
+            TimeoutBackoff::new(self, base, max, factor)
+        }
+    }
+
+    cfg_rt! {
+        /// Return an error if the next item is not yielded within a duration
+        /// computed from the previous item.
+        ///
+        /// This is like [`timeout`](StreamExt::timeout), but rather than using a
+        /// fixed duration, `f` is called with every item the stream yields to
+        /// compute how long the next item may take. This is useful for protocols
+        /// where each message advertises when the next one is due. No timeout
+        /// applies until the first item has been yielded. A duration of zero
+        /// requires the next item to be ready right away.
+        ///
+        /// As with `timeout`, a timeout does not end the stream: an error is
+        /// yielded and the last computed duration starts over.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use futures_lite::prelude::*;
+        /// use futures_time::prelude::*;
+        /// use futures_time::time::Duration;
+        /// use futures_lite::stream;
+        ///
+        /// fn main() {
+        ///     async_io::block_on(async {
+        ///         let mut s = stream::once(Duration::from_millis(10))
+        ///             .chain(stream::pending())
+        ///             .timeout_dynamic(|dur| *dur); // the item says when the next one is due
+        ///         assert!(s.next().await.unwrap().is_ok());
+        ///         assert!(s.next().await.unwrap().is_err());
+        ///     });
+        /// }
+        /// ```
+        fn timeout_dynamic<F>(self, f: F) -> TimeoutDynamic<Self, F>
+        where
+            Self: Sized,
+            F: FnMut(&Self::Item) -> Duration,
+        {
+            TimeoutDynamic::new(self, f)
+        }
+    }
+
+    /// Pair each item with the latest item yielded by another stream.
+    ///
+    /// The other stream is polled eagerly every time this stream is polled,
+    /// so the latest value is kept fresh, but this stream never waits for the
+    /// other stream to yield. Items which arrive before the other stream has
+    /// yielded anything are paired with `None`; use
+    /// [`WithLatestFrom::skip_without_latest`] to drop them instead. Once the
+    /// other stream ends, its last item keeps being used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::iter(0..2)
+    ///             .with_latest_from(stream::iter(vec!["a", "b"]))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![(0, Some("b")), (1, Some("b"))]);
+    ///     })
+    /// }
+    /// ```
+    fn with_latest_from<U>(self, other: U) -> WithLatestFrom<Self, U::IntoStream>
+    where
+        Self: Sized,
+        U: IntoStream,
+    {
+        WithLatestFrom::new(self, other.into_stream())
+    }
+
+    /// Yield items until a future resolves.
+    ///
+    /// Once the future resolves the stream ends, and the underlying stream is
+    /// no longer polled. Unlike [`timeout`](StreamExt::timeout) the items are
+    /// not wrapped in a `Result`, which makes this well suited for graceful
+    /// shutdown: any future can be used, such as a channel which receives a
+    /// shutdown message.
+    ///
+    /// By default any work the underlying stream is doing to produce its next
+    /// item is dropped when the future resolves. Use [`TakeUntil::graceful`]
+    /// to let items which are in flight finish first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_time::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let mut counter = 0;
+    ///         stream::interval(Duration::from_millis(20))
+    ///             .take_until(Duration::from_millis(50))
+    ///             .for_each(|_| counter += 1)
+    ///             .await;
+    ///
+    ///         assert_eq!(counter, 2);
+    ///     })
+    /// }
+    /// ```
+    fn take_until<D>(self, deadline: D) -> TakeUntil<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+    {
+        TakeUntil::new(self, deadline.into_future())
+    }
+
+    /// Yield the last item received once a window, started by the first
+    /// item, has passed.
+    ///
+    /// When an item arrives and no window is open, a window is started. Items
+    /// that arrive during the window replace each other, and once the window
+    /// ends the last one is yielded. Then the stream stays quiet until the next
+    /// item starts a new window. Unlike [`sample()`], which uses a fixed grid,
+    /// the window is started by item arrival. Unlike [`throttle()`], which
+    /// yields the first item of a window, the last item is yielded. And unlike
+    /// [`debounce()`], the window is not extended by later items.
+    ///
+    /// [`sample()`]: `StreamExt::sample`
+    /// [`throttle()`]: `StreamExt::throttle`
+    /// [`debounce()`]: `StreamExt::debounce`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::iter(0..3)  // a burst of items
+    ///             .audit(Duration::from_millis(20))
+    ///             .collect()
+    ///             .await;
+    ///
+    ///         assert_eq!(items, vec![2]); // only the last item is received
+    ///     })
+    /// }
+    /// ```
+    fn audit<D>(self, window: D) -> Audit<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+        D::IntoFuture: Timer,
+    {
+        Audit::new(self, window.into_future())
+    }
+
+    /// Report each time a stream does not yield an item within a given time
+    /// span, without ending the stream.
+    ///
+    /// Items of the underlying stream are yielded as [`TimedItem::Item`]. When
+    /// the deadline passes first, [`TimedItem::Idle`] is yielded with the time
+    /// elapsed since the last item, or since the stream was created if there
+    /// was none. The deadline is reset after every item and every idle report.
+    ///
+    /// Unlike [`timeout()`], an idle period is not reported as an error, which
+    /// makes this useful for showing progress such as "still waiting..." while
+    /// a stream is quiet.
+    ///
+    /// [`timeout()`]: `StreamExt::timeout`
+    /// [`TimedItem::Item`]: crate::stream::TimedItem::Item
+    /// [`TimedItem::Idle`]: crate::stream::TimedItem::Idle
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::stream::TimedItem;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let mut stream = stream::once("meow")
+    ///             .delay(Duration::from_millis(100))
+    ///             .idle_timeout(Duration::from_millis(60));
+    ///
+    ///         assert!(matches!(stream.next().await, Some(TimedItem::Idle(_))));
+    ///         assert_eq!(stream.next().await, Some(TimedItem::Item("meow")));
+    ///     });
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    fn idle_timeout<D>(self, deadline: D) -> IdleTimeout<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+        D::IntoFuture: Timer,
+    {
+        IdleTimeout::new(self, deadline.into_future())
+    }
+
+    /// Suppress consecutive equal items, but repeat the last item whenever
+    /// the deadline passes without one being yielded.
+    ///
+    /// An item is only yielded if it differs from the item yielded before it.
+    /// When the deadline passes first, the last yielded item is yielded again
+    /// to show that it is still current. The deadline is reset after every
+    /// item which is yielded, including repeats. Nothing is repeated before
+    /// the first item has been yielded.
+    ///
+    /// This is useful for telemetry, where downstream consumers need regular
+    /// confirmation of a value without being flooded by duplicate readings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let readings: Vec<_> = stream::iter(vec![20, 20, 21, 21, 20])
+    ///             .dedup_timeout(Duration::from_secs(1))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(readings, vec![20, 21, 20]);
+    ///     });
+    /// }
+    /// ```
+    fn dedup_timeout<D>(self, deadline: D) -> DedupTimeout<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        Self::Item: PartialEq + Clone,
+        D: IntoFuture,
+        D::IntoFuture: Timer,
+    {
+        DedupTimeout::new(self, deadline.into_future())
+    }
+
+    /// Delay the start of a stream by an offset.
+    ///
+    /// This behaves the same as [`delay()`]: the underlying stream is not
+    /// polled until the offset has passed. It exists to make the intent clear
+    /// when starting a group of similar streams at different offsets, so that
+    /// they do not all do their work at the same time.
+    ///
+    /// Note that the offset only delays polling: timers which the underlying
+    /// stream started when it was created keep running during the offset. An
+    /// [`interval`](crate::stream::interval) that is staggered by less than
+    /// its period therefore still fires at its original phase, while one that
+    /// is staggered by more than its period yields right away once the offset
+    /// has passed and keeps that phase from then on.
+    ///
+    /// [`delay()`]: `StreamExt::delay`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::{Instant, Duration};
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let now = Instant::now();
+    ///         let streams = vec![stream::once(0), stream::once(1), stream::once(2)];
+    ///         let mut staggered: Vec<_> = streams
+    ///             .into_iter()
+    ///             .enumerate()
+    ///             .map(|(i, s)| s.stagger(Duration::from_millis(50 * i as u64)))
+    ///             .collect();
+    ///
+    ///         let last = staggered.pop().unwrap();
+    ///         assert_eq!(last.collect::<Vec<_>>().await, vec![2]);
+    ///         assert!(now.elapsed() >= Duration::from_millis(100));
+    ///     });
+    /// }
+    /// ```
+    fn stagger<D>(self, offset: D) -> Delay<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+    {
+        Delay::new(self, offset.into_future())
+    }
+
+    /// End the stream once a deadline passes.
+    ///
+    /// The deadline is not reset by items, so this bounds the total time the
+    /// stream runs for. When the deadline passes the stream ends without an
+    /// error. The deadline can be a [`Duration`](crate::time::Duration), or
+    /// any other type which implements [`IntoFuture`].
+    ///
+    /// This is the same operator as [`take_until()`]. Compared to
+    /// [`timeout()`], which resets its deadline after every item and yields an
+    /// error each time it passes, `timeout_once` limits the total duration and
+    /// ends the stream quietly. Use [`timeout_total()`] to yield an error
+    /// when the deadline ends the stream.
+    ///
+    /// [`take_until()`]: `StreamExt::take_until`
+    /// [`timeout()`]: `StreamExt::timeout`
+    /// [`timeout_total()`]: `StreamExt::timeout_total`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_time::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         // `timeout` resets after every item, so a steady stream never times out.
+    ///         let items: Vec<_> = stream::interval(Duration::from_millis(20))
+    ///             .take(4)
+    ///             .timeout(Duration::from_millis(50))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items.len(), 4);
+    ///         assert!(items.iter().all(|item| item.is_ok()));
+    ///
+    ///         // `timeout_once` limits the total duration, and ends without an error.
+    ///         let items: Vec<_> = stream::interval(Duration::from_millis(20))
+    ///             .take(4)
+    ///             .timeout_once(Duration::from_millis(50))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items.len(), 2);
+    ///     })
+    /// }
+    /// ```
+    fn timeout_once<D>(self, deadline: D) -> TimeoutOnce<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+    {
+        TakeUntil::new(self, deadline.into_future())
+    }
+
+    /// Yield items until a total time budget runs out, then yield an error and
+    /// end the stream.
+    ///
+    /// The budget is not reset by items, so this bounds the total time the
+    /// stream runs for. When the budget runs out a single
+    /// [`TimeoutError`](crate::future::TimeoutError) is yielded, after which
+    /// the stream ends. If the stream ends within the budget no error is
+    /// yielded. This differs from [`timeout_once()`], which ends the stream
+    /// quietly, and from [`timeout()`], which resets its deadline after every
+    /// item and keeps going after an error. Use this to get as many items as
+    /// possible within a budget, while knowing whether the budget was hit.
+    ///
+    /// [`timeout_once()`]: `StreamExt::timeout_once`
+    /// [`timeout()`]: `StreamExt::timeout`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_time::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::interval(Duration::from_millis(20))
+    ///             .timeout_total(Duration::from_millis(50))
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items.len(), 3);
+    ///         assert!(items.last().unwrap().is_err()); // the budget was hit
+    ///     })
+    /// }
+    /// ```
+    #[doc(alias = "timeout_once_err")]
+    fn timeout_total<D>(self, budget: D) -> TimeoutTotal<Self, D::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+    {
+        TimeoutTotal::new(self, budget.into_future())
+    }
+
+    /// Return an error if a stream does not yield an item within a time span,
+    /// and end it once a grace period has passed since its first item.
+    ///
+    /// Two timers apply to the stream:
+    ///
+    /// - The `idle` timer is armed between items, like with
+    ///   [`timeout()`](StreamExt::timeout). Every item resets it. When it
+    ///   fires an error is yielded, the timer is reset, and the stream
+    ///   continues.
+    /// - The `grace` timer is armed when the first item is received, and is
+    ///   never reset. When it fires an error is yielded, and the stream ends.
+    ///
+    /// This suits responses which should start quickly, and then finish within
+    /// a bound once they have started. Both errors are a
+    /// [`TimeoutError`](crate::future::TimeoutError), which report the
+    /// duration of the timer that fired through
+    /// [`TimeoutError::configured`](crate::future::TimeoutError::configured).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::stream;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::interval(Duration::from_millis(20))
+    ///             .timeout_graced(Duration::from_millis(100), Duration::from_millis(50))
+    ///             .collect()
+    ///             .await;
+    ///         assert!(items.last().unwrap().is_err()); // the grace period ended
+    ///     })
+    /// }
+    /// ```
+    fn timeout_graced<D, G>(
+        self,
+        idle: D,
+        grace: G,
+    ) -> TimeoutGraced<Self, D::IntoFuture, G::IntoFuture>
+    where
+        Self: Sized,
+        D: IntoFuture,
+        D::IntoFuture: Timer,
+        G: IntoFuture,
+        G::IntoFuture: Timer,
+    {
+        TimeoutGraced::new(self, idle.into_future(), grace.into_future())
+    }
+
+    cfg_rt! {
+        /// Delay items to enforce a sustained rate, allowing short bursts.
+        ///
+        /// This is a token bucket: every item takes a token, one token is added
+        /// every `rate`, and at most `burst` tokens are kept. The bucket starts
+        /// out full. When no tokens are left the underlying stream is not polled
+        /// until the next token is added, so items are delayed rather than
+        /// dropped, and no items are buffered.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `burst` is zero.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use futures_lite::prelude::*;
+        /// use futures_time::prelude::*;
+        /// use futures_time::time::{Duration, Instant};
+        /// use futures_lite::stream;
+        ///
+        /// fn main() {
+        ///     async_io::block_on(async {
+        ///         let now = Instant::now();
+        ///         let items: Vec<_> = stream::iter(0..4)
+        ///             .rate_limit(Duration::from_millis(10), 2) // two right away, then one every 10ms
+        ///             .collect()
+        ///             .await;
+        ///         assert_eq!(items, vec![0, 1, 2, 3]);
+        ///         assert!(now.elapsed() >= Duration::from_millis(20));
+        ///     })
+        /// }
+        /// ```
+        fn rate_limit(self, rate: Duration, burst: usize) -> RateLimit<Self>
+        where
+            Self: Sized,
+        {
+            RateLimit::new(self, rate, burst)
+        }
+    }
 }
 
 impl<S> StreamExt for S where S: Stream {}
@@ -0,0 +1,263 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::Timer;
+
+pin_project! {
+    /// End the stream once a future resolves.
+    ///
+    /// This `struct` is created by the [`take_until`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`take_until`]: crate::stream::StreamExt::take_until
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct TakeUntil<S, F> {
+        #[pin]
+        stream: S,
+        #[pin]
+        deadline: F,
+        in_flight: fn(&S) -> bool,
+        cancelled: bool,
+        done: bool,
+        start: Option<fn(Pin<&mut F>)>,
+    }
+}
+
+/// A stream which ends once a deadline passes.
+///
+/// This type is returned by the [`timeout_once`] method on [`StreamExt`], and
+/// behaves the same as [`TakeUntil`]. See its documentation for more.
+///
+/// [`timeout_once`]: crate::stream::StreamExt::timeout_once
+/// [`StreamExt`]: crate::stream::StreamExt
+pub type TimeoutOnce<S, D> = TakeUntil<S, D>;
+
+impl<S, F> TakeUntil<S, F> {
+    pub(crate) fn new(stream: S, deadline: F) -> Self {
+        Self {
+            stream,
+            deadline,
+            in_flight: |_| false,
+            cancelled: false,
+            done: false,
+            start: None,
+        }
+    }
+
+    /// Let the item which is in flight finish when the future resolves.
+    ///
+    /// By default the stream ends as soon as the future resolves, dropping any
+    /// work the underlying stream was doing to produce its next item. This is a
+    /// hard cancel. With this option `in_flight` is asked whether the
+    /// underlying stream is part way through producing an item. If it is, the
+    /// stream keeps being polled, and its items are yielded, until `in_flight`
+    /// returns `false`, after which the stream ends. This is a soft cancel,
+    /// which stops new work from starting but lets current work finish.
+    ///
+    /// A pending underlying stream can either be busy or idle, so only the
+    /// stream itself can tell whether an item is in flight. If `in_flight`
+    /// returns `false`, the stream ends right away, even while it is waiting
+    /// for its next item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::{channel, future};
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let (cancel, receiver) = future::signal();
+    ///         let (sender, jobs) = channel::unbounded();
+    ///         let mut jobs = jobs.take_until(receiver).graceful(|jobs| !jobs.is_empty());
+    ///
+    ///         sender.send(0).await.unwrap();
+    ///         sender.send(1).await.unwrap();
+    ///         cancel.notify();
+    ///
+    ///         // Jobs which were already queued are finished.
+    ///         assert_eq!(jobs.next().await, Some(0));
+    ///         assert_eq!(jobs.next().await, Some(1));
+    ///         assert_eq!(jobs.next().await, None);
+    ///     })
+    /// }
+    /// ```
+    pub fn graceful(mut self, in_flight: fn(&S) -> bool) -> Self {
+        self.in_flight = in_flight;
+        self
+    }
+}
+
+impl<S, F: Timer> TakeUntil<S, F> {
+    /// Start counting down when the stream is first polled, rather than when
+    /// it was created.
+    ///
+    /// By default a relative deadline, such as the one created by
+    /// [`timeout_once`], counts from the moment the method is called, so time
+    /// the stream spends waiting to be polled eats into it. With this option
+    /// the timer is reset on the first poll instead.
+    ///
+    /// [`timeout_once`]: crate::stream::StreamExt::timeout_once
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::task;
+    /// use futures_time::time::Duration;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let mut stream = futures_lite::stream::once(1)
+    ///             .delay(Duration::from_millis(20))
+    ///             .timeout_once(Duration::from_millis(100))
+    ///             .lazy_start();
+    ///         task::sleep(Duration::from_millis(150)).await;
+    ///         assert_eq!(stream.next().await, Some(1));
+    ///     });
+    /// }
+    /// ```
+    pub fn lazy_start(mut self) -> Self {
+        self.start = Some(|deadline| deadline.reset_timer());
+        self
+    }
+}
+
+impl<S: Stream, F: Future> Stream for TakeUntil<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        assert!(!*this.done, "stream polled after completion");
+
+        if let Some(start) = this.start.take() {
+            start(this.deadline.as_mut());
+        }
+
+        if !*this.cancelled && this.deadline.as_mut().poll(cx).is_ready() {
+            *this.cancelled = true;
+        }
+
+        // In graceful mode an item which is in flight is allowed to finish.
+        if *this.cancelled && !(this.in_flight)(this.stream.as_ref().get_ref()) {
+            *this.done = true;
+            return Poll::Ready(None);
+        }
+
+        match this.stream.poll_next(cx) {
+            Poll::Ready(None) => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+            poll => poll,
+        }
+    }
+}
+
+impl<S: Stream, F: Future> FusedStream for TakeUntil<S, F> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::channel;
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn ends_on_signal() {
+        crate::utils::block_on(async {
+            let (send, mut recv) = channel::bounded::<()>(1);
+            let mut stream =
+                crate::stream::interval(Duration::from_millis(5)).take_until(recv.next());
+
+            assert!(stream.next().await.is_some());
+            drop(send);
+            assert!(stream.next().await.is_none());
+        })
+    }
+
+    #[test]
+    fn graceful_finishes_item_in_flight() {
+        crate::utils::block_on(async {
+            let jobs = |graceful| {
+                let (cancel, receiver) = crate::future::signal();
+                let (sender, jobs) = channel::unbounded();
+                sender.try_send(0).unwrap();
+                sender.try_send(1).unwrap();
+                cancel.notify();
+                let stream = jobs.take_until(receiver);
+                let stream = match graceful {
+                    true => stream.graceful(|jobs| !jobs.is_empty()),
+                    false => stream,
+                };
+                // Keep the channel open, so only the cancellation ends it.
+                (stream, sender)
+            };
+
+            assert_eq!(jobs(true).0.collect::<Vec<_>>().await, vec![0, 1]);
+            assert_eq!(jobs(false).0.collect::<Vec<_>>().await, Vec::<i32>::new());
+        })
+    }
+
+    #[test]
+    fn graceful_ends_idle_stream() {
+        crate::utils::block_on(async {
+            let (cancel, receiver) = crate::future::signal();
+            let (_sender, jobs) = channel::unbounded::<i32>();
+            let stream = jobs.take_until(receiver).graceful(|jobs| !jobs.is_empty());
+            let cancel = async move {
+                crate::task::sleep(Duration::from_millis(10)).await;
+                cancel.notify();
+            };
+
+            let (items, ()) = futures_lite::future::zip(stream.collect::<Vec<_>>(), cancel).await;
+            assert!(items.is_empty());
+        })
+    }
+
+    #[test]
+    fn lazy_start() {
+        futures_lite::future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let budget = Duration::from_millis(10);
+            let deadline = crate::task::Sleep::with_clock(budget, clock.clone());
+            let mut stream = futures_lite::stream::pending::<()>()
+                .timeout_once(deadline)
+                .lazy_start();
+
+            // Time spent before the first poll is not counted.
+            clock.advance(Duration::from_millis(20));
+            assert_eq!(futures_lite::future::poll_once(stream.next()).await, None);
+            clock.advance(budget);
+            assert_eq!(
+                futures_lite::future::poll_once(stream.next()).await,
+                Some(None)
+            );
+        })
+    }
+
+    #[test]
+    fn timeout_once_with_duration() {
+        crate::utils::block_on(async {
+            let mut counter = 0;
+            crate::stream::interval(Duration::from_millis(20))
+                .timeout_once(Duration::from_millis(50))
+                .for_each(|_| counter += 1)
+                .await;
+            assert_eq!(counter, 2);
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::IntoFuture;
+
+pin_project! {
+    /// Stream one stream, then wait for a deadline, then stream another.
+    ///
+    /// This `struct` is created by the [`then_after`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`then_after`]: crate::stream::StreamExt::then_after
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct ThenAfter<S, D: IntoFuture, U> {
+        #[pin]
+        first: S,
+        gap: Option<D>,
+        #[pin]
+        deadline: Option<D::IntoFuture>,
+        #[pin]
+        next: U,
+        state: State,
+    }
+}
+
+/// Internal state.
+#[derive(Debug)]
+enum State {
+    /// The first stream is yielding items.
+    First,
+    /// The first stream has ended, and the deadline has not passed yet.
+    Gap,
+    /// The second stream is yielding items.
+    Next,
+    /// The second stream has ended.
+    Done,
+}
+
+impl<S, D: IntoFuture, U> ThenAfter<S, D, U> {
+    pub(crate) fn new(first: S, gap: D, next: U) -> Self {
+        Self {
+            first,
+            gap: Some(gap),
+            deadline: None,
+            next,
+            state: State::First,
+        }
+    }
+}
+
+impl<S, D, U> fmt::Debug for ThenAfter<S, D, U>
+where
+    S: fmt::Debug,
+    D: IntoFuture + fmt::Debug,
+    D::IntoFuture: fmt::Debug,
+    U: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThenAfter")
+            .field("first", &self.first)
+            .field("gap", &self.gap)
+            .field("deadline", &self.deadline)
+            .field("next", &self.next)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<S, D, U> Stream for ThenAfter<S, D, U>
+where
+    S: Stream,
+    D: IntoFuture,
+    U: Stream<Item = S::Item>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            match this.state {
+                State::First => match this.first.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
+                    // The deadline is only created once the first stream has
+                    // ended, so a duration is counted from the junction.
+                    Poll::Ready(None) => {
+                        let gap = this.gap.take().unwrap();
+                        this.deadline.set(Some(gap.into_future()));
+                        *this.state = State::Gap;
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                State::Gap => match this.deadline.as_mut().as_pin_mut().unwrap().poll(cx) {
+                    Poll::Ready(_) => {
+                        this.deadline.set(None);
+                        *this.state = State::Next;
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                State::Next => match this.next.as_mut().poll_next(cx) {
+                    Poll::Ready(None) => {
+                        *this.state = State::Done;
+                        return Poll::Ready(None);
+                    }
+                    poll => return poll,
+                },
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<S, D, U> FusedStream for ThenAfter<S, D, U>
+where
+    S: Stream,
+    D: IntoFuture,
+    U: Stream<Item = S::Item>,
+{
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::{Duration, Instant};
+    use futures_core::stream::FusedStream;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn gap_between_streams() {
+        crate::utils::block_on(async {
+            let gap = Duration::from_millis(50);
+            let mut stream = futures_lite::stream::iter(0..2)
+                .chain(futures_lite::stream::once(2).delay(Duration::from_millis(20)))
+                .then_after(gap, futures_lite::stream::iter(3..5));
+
+            let mut items = vec![];
+            let mut last_first = None;
+            let mut first_next = None;
+            while let Some(item) = stream.next().await {
+                match item {
+                    2 => last_first = Some(Instant::now()),
+                    3 => first_next = Some(Instant::now()),
+                    _ => {}
+                }
+                items.push(item);
+            }
+            assert_eq!(items, vec![0, 1, 2, 3, 4]);
+            assert!(stream.is_terminated());
+            assert!(first_next.unwrap() - last_first.unwrap() >= gap);
+        })
+    }
+}
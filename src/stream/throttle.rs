@@ -1,12 +1,19 @@
 use pin_project_lite::pin_project;
 
-use futures_core::stream::Stream;
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::stream::{FusedStream, Stream};
+
+cfg_rt! {
+    use super::Interval;
+    use crate::time::Clock;
+}
 
 pin_project! {
     /// Filter out all items after the first for a specified time.
     ///
+    /// When the interval stream ends, the stream ends too.
+    ///
     /// This `struct` is created by the [`throttle`] method on [`StreamExt`]. See its
     /// documentation for more.
     ///
@@ -14,23 +21,179 @@ pin_project! {
     /// [`StreamExt`]: crate::stream::StreamExt
     #[derive(Debug)]
     #[must_use = "streams do nothing unless polled or .awaited"]
-    pub struct Throttle<S: Stream, I> {
+    pub struct Throttle<S: Stream, I: Stream> {
         #[pin]
         stream: S,
         #[pin]
         interval: I,
         state: State,
         budget: usize,
+        window: fn(&I::Item) -> usize,
+        bypass: fn(&S::Item) -> bool,
+        trailing: bool,
+        reset: fn(Pin<&mut I>),
+        backpressure: bool,
+        pending: Option<S::Item>,
     }
 }
 
-impl<S: Stream, I> Throttle<S, I> {
+impl<S: Stream, I: Stream> Throttle<S, I> {
     pub(crate) fn new(stream: S, interval: I) -> Self {
+        Self::with_window(stream, interval, |_| 1)
+    }
+
+    /// Create a throttle which reads the budget of the next window from each
+    /// item of the interval stream.
+    pub(crate) fn with_window(stream: S, interval: I, window: fn(&I::Item) -> usize) -> Self {
         Self {
             state: State::Streaming(0),
             stream,
             interval,
             budget: 1,
+            window,
+            bypass: |_| false,
+            trailing: false,
+            reset: |_| {},
+            backpressure: false,
+            pending: None,
+        }
+    }
+
+    /// Yield items for which `bypass` returns `true` right away, without
+    /// counting them against the budget of the interval.
+    pub(crate) fn with_bypass(mut self, bypass: fn(&S::Item) -> bool) -> Self {
+        self.bypass = bypass;
+        self
+    }
+
+    /// Yield the first item of each interval, and drop the rest.
+    ///
+    /// The first interval starts when the throttle is created, so the very
+    /// first item is yielded as soon as it arrives. This is the default, and
+    /// undoes [`suppress_first`](Throttle::suppress_first).
+    pub fn leading(mut self) -> Self {
+        self.trailing = false;
+        self.state = State::Streaming(0);
+        self
+    }
+
+    /// Drop all items which arrive before the first interval has ended, then
+    /// yield the first item of each interval after that.
+    ///
+    /// With a time-based interval, this means the first item can be yielded
+    /// one interval after the throttle was created at the earliest. When
+    /// combined with [`backpressure`](Throttle::backpressure), the first item
+    /// is held back in the underlying stream until then rather than dropped.
+    /// This turns off [`trailing`](Throttle::trailing).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::iter(0..3)
+    ///             .throttle(Duration::from_millis(10))
+    ///             .suppress_first()
+    ///             .collect()
+    ///             .await;
+    ///         assert!(items.is_empty());
+    ///     })
+    /// }
+    /// ```
+    pub fn suppress_first(mut self) -> Self {
+        self.trailing = false;
+        // Start out with the budget of the first interval used up.
+        self.state = State::Streaming(self.budget);
+        self
+    }
+
+    /// Stop pulling items from the underlying stream until the interval allows
+    /// another item to be yielded, rather than dropping them.
+    ///
+    /// By default items which arrive within an interval are pulled from the
+    /// underlying stream and dropped. With this option the underlying stream is
+    /// not polled again until the next interval, so a fast producer is slowed
+    /// down to the pace of the interval and no items are dropped or buffered.
+    /// This yields the first item of each interval, like
+    /// [`leading`](Throttle::leading), and turns off
+    /// [`trailing`](Throttle::trailing).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_lite::prelude::*;
+    /// use futures_time::prelude::*;
+    /// use futures_time::time::Duration;
+    /// use futures_lite::stream;
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let items: Vec<_> = stream::iter(0..3)
+    ///             .throttle(Duration::from_millis(10))
+    ///             .backpressure()
+    ///             .collect()
+    ///             .await;
+    ///         assert_eq!(items, vec![0, 1, 2]);
+    ///     })
+    /// }
+    /// ```
+    pub fn backpressure(mut self) -> Self {
+        self.trailing = false;
+        self.backpressure = true;
+        self
+    }
+}
+
+cfg_rt! {
+    impl<S: Stream, C: Clock> Throttle<S, Interval<C>>
+    where
+        Interval<C>: Unpin,
+    {
+        /// Yield the last item of each burst once the interval has passed since
+        /// the first item of the burst, and drop the rest.
+        ///
+        /// The window is relative to when items arrive: the first item after a
+        /// quiet period restarts the interval, and whatever item is latest when
+        /// it ticks is yielded. Unlike [`debounce()`], later items in the burst
+        /// do not push the window back, so a steady stream of items still
+        /// yields one item per interval. This is the same window as
+        /// [`audit()`] uses.
+        ///
+        /// If the stream ends while an item is waiting for the interval to end, it
+        /// is yielded right away.
+        ///
+        /// [`audit()`]: crate::stream::StreamExt::audit
+        /// [`debounce()`]: crate::stream::StreamExt::debounce
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use futures_lite::prelude::*;
+        /// use futures_time::prelude::*;
+        /// use futures_time::time::Duration;
+        /// use futures_lite::stream;
+        ///
+        /// fn main() {
+        ///     async_io::block_on(async {
+        ///         let items: Vec<_> = stream::iter(0..3)
+        ///             .throttle(Duration::from_millis(100))
+        ///             .trailing()
+        ///             .collect()
+        ///             .await;
+        ///         assert_eq!(items, vec![2]);
+        ///     })
+        /// }
+        /// ```
+        pub fn trailing(mut self) -> Self {
+            self.trailing = true;
+            self.reset = |interval| interval.get_mut().reset();
+            self.backpressure = false;
+            self
         }
     }
 }
@@ -56,13 +219,30 @@ impl<S: Stream, I: Stream> Stream for Throttle<S, I> {
         match this.state {
             // The underlying stream is yielding items.
             State::Streaming(count) => {
-                // Poll the underlying stream until we get to `Poll::Pending`.
+                // Poll the underlying stream until we get to `Poll::Pending`,
+                // or until the budget runs out when applying backpressure.
                 loop {
+                    if *this.backpressure && *count >= *this.budget {
+                        break;
+                    }
                     match this.stream.as_mut().poll_next(cx) {
+                        Poll::Ready(Some(value)) if (this.bypass)(&value) => {
+                            return Poll::Ready(Some(value));
+                        }
                         Poll::Ready(Some(value)) => {
-                            if count < this.budget {
+                            if *this.trailing {
+                                // The first item of a burst opens a new window.
+                                if this.pending.replace(value).is_some() {
+                                    trace!("throttle dropped item");
+                                } else {
+                                    (this.reset)(this.interval.as_mut());
+                                }
+                            } else if count < this.budget {
                                 slot = Some(value);
                                 *count += 1;
+                                break;
+                            } else {
+                                trace!("throttle dropped item");
                             }
                         }
                         Poll::Ready(None) => {
@@ -74,15 +254,40 @@ impl<S: Stream, I: Stream> Stream for Throttle<S, I> {
                 }
 
                 // After the stream, always poll the interval timer.
-                let _ = this
-                    .interval
-                    .as_mut()
-                    .poll_next(cx)
-                    .map(move |_| match this.state {
-                        State::Streaming(count) => *count = 0, // reset the counter
+                if let Poll::Ready(tick) = this.interval.as_mut().poll_next(cx) {
+                    match tick {
+                        Some(tick) => *this.budget = (this.window)(&tick),
+                        // The interval stream has ended, so there are no more
+                        // windows. End the stream once the trailing item, if
+                        // any, has been flushed.
+                        None => *this.state = State::StreamDone,
+                    }
+                    match this.state {
+                        State::Streaming(count) => {
+                            *count = 0; // reset the counter
+                            if let Some(item) = this.pending.take() {
+                                slot = Some(item);
+                            }
+                            // Poll again so the interval registers for its
+                            // next tick, or reports that it has ended. With
+                            // backpressure this also polls the underlying
+                            // stream, which was left alone since the budget
+                            // ran out.
+                            if slot.is_none() {
+                                cx.waker().wake_by_ref();
+                            }
+                        }
                         State::StreamDone => cx.waker().wake_by_ref(),
                         State::AllDone => {}
-                    });
+                    }
+                }
+
+                // Flush a trailing item if the stream has ended.
+                if let State::StreamDone = this.state {
+                    if let Some(item) = this.pending.take() {
+                        slot = Some(item);
+                    }
+                }
                 match slot {
                     Some(item) => Poll::Ready(Some(item)),
                     None => Poll::Pending,
@@ -101,15 +306,24 @@ impl<S: Stream, I: Stream> Stream for Throttle<S, I> {
     }
 }
 
-#[cfg(test)]
+impl<S: Stream, I: Stream> FusedStream for Throttle<S, I> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::AllDone)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
 mod test {
     use crate::prelude::*;
-    use crate::time::Duration;
+    use crate::stream::Interval;
+    use crate::time::{Duration, Instant};
+    use futures_core::stream::FusedStream;
+    use futures_lite::future;
     use futures_lite::prelude::*;
 
     #[test]
     fn smoke() {
-        async_io::block_on(async {
+        crate::utils::block_on(async {
             let interval = Duration::from_millis(100);
             let throttle = Duration::from_millis(300);
 
@@ -126,4 +340,177 @@ mod test {
             assert_eq!(counter, expected);
         })
     }
+
+    #[test]
+    fn trailing() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = crate::stream::interval(Duration::from_millis(100))
+                .take(4)
+                .enumerate()
+                .map(|(i, _)| i)
+                .throttle(Duration::from_millis(250))
+                .trailing()
+                .collect()
+                .await;
+
+            // The first item opens a window which ends after the third, and
+            // the fourth is flushed when the stream ends.
+            assert_eq!(items, vec![2, 3]);
+        })
+    }
+
+    #[test]
+    fn trailing_window_starts_on_arrival() {
+        futures_lite::future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let period = Duration::from_millis(10);
+            let (sender, receiver) = crate::channel::unbounded();
+            let mut stream = receiver
+                .throttle(Interval::with_clock(period, clock.clone()))
+                .trailing();
+
+            // A burst starting in the middle of the first grid interval.
+            clock.advance(Duration::from_millis(7));
+            sender.try_send(0).unwrap();
+            sender.try_send(1).unwrap();
+            assert_eq!(future::poll_once(stream.next()).await, None);
+
+            // The grid tick passes without flushing the burst.
+            clock.advance(Duration::from_millis(5));
+            sender.try_send(2).unwrap();
+            assert_eq!(future::poll_once(stream.next()).await, None);
+
+            // One interval after the first item, the latest item is yielded.
+            clock.advance(Duration::from_millis(5));
+            assert_eq!(future::poll_once(stream.next()).await, Some(Some(2)));
+            assert_eq!(future::poll_once(stream.next()).await, None);
+        })
+    }
+
+    #[test]
+    fn backpressure() {
+        use core::cell::Cell;
+
+        crate::utils::block_on(async {
+            let pulled = Cell::new(0);
+            let items: Vec<_> = futures_lite::stream::iter(0..)
+                .inspect(|_| pulled.set(pulled.get() + 1))
+                .throttle(Duration::from_millis(10))
+                .backpressure()
+                .take(3)
+                .collect()
+                .await;
+
+            assert_eq!(items, vec![0, 1, 2]);
+            assert_eq!(pulled.get(), 3);
+        })
+    }
+
+    #[test]
+    fn variable_budget() {
+        crate::utils::block_on(async {
+            let control = crate::stream::interval(Duration::from_millis(20))
+                .take(4)
+                .zip(futures_lite::stream::iter(vec![2, 0, 3, 1]))
+                .map(|(_, budget)| budget);
+            let start = Instant::now();
+            let items: Vec<_> = futures_lite::stream::iter(0..)
+                .throttle_with(control)
+                .backpressure()
+                .take(6)
+                .collect()
+                .await;
+
+            assert_eq!(items, vec![0, 1, 2, 3, 4, 5]);
+            assert!(start.elapsed() >= Duration::from_millis(60));
+        })
+    }
+
+    #[test]
+    fn is_terminated() {
+        crate::utils::block_on(async {
+            let mut stream = crate::stream::interval(Duration::from_millis(5))
+                .take(2)
+                .throttle(Duration::from_millis(20));
+            assert!(!stream.is_terminated());
+            while stream.next().await.is_some() {}
+            assert!(stream.is_terminated());
+        })
+    }
+
+    #[test]
+    fn forwards_errors() {
+        crate::utils::block_on(async {
+            let items: Vec<Result<u8, &str>> =
+                futures_lite::stream::iter(vec![Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)])
+                    .throttle_ok(Duration::from_millis(20))
+                    .collect()
+                    .await;
+            assert_eq!(items, vec![Ok(1), Err("a"), Err("b")]);
+        })
+    }
+
+    #[test]
+    fn interval_end_ends_stream() {
+        crate::utils::block_on(async {
+            let source = || {
+                futures_lite::stream::iter(0..3)
+                    .chain(futures_lite::stream::once(3).delay(Duration::from_millis(75)))
+                    .chain(futures_lite::stream::pending())
+            };
+            let trigger = || crate::stream::interval(Duration::from_millis(50)).take(3);
+
+            let mut stream = source().throttle(trigger());
+            let mut items = vec![];
+            while let Some(item) = stream.next().await {
+                items.push(item);
+            }
+            assert_eq!(items, vec![0, 3]);
+            assert!(stream.is_terminated());
+        })
+    }
+
+    #[test]
+    fn first_item_leading() {
+        futures_lite::future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let period = Duration::from_millis(10);
+            let (sender, receiver) = crate::channel::unbounded();
+            let mut stream = receiver.throttle(Interval::with_clock(period, clock.clone()));
+
+            sender.try_send(0).unwrap();
+            sender.try_send(1).unwrap();
+            assert_eq!(future::poll_once(stream.next()).await, Some(Some(0)));
+            assert_eq!(future::poll_once(stream.next()).await, None);
+
+            clock.advance(period);
+            assert_eq!(future::poll_once(stream.next()).await, None);
+            sender.try_send(2).unwrap();
+            assert_eq!(future::poll_once(stream.next()).await, Some(Some(2)));
+        })
+    }
+
+    #[test]
+    fn suppress_first() {
+        futures_lite::future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let period = Duration::from_millis(10);
+            let (sender, receiver) = crate::channel::unbounded();
+            let mut stream = receiver
+                .throttle(Interval::with_clock(period, clock.clone()))
+                .suppress_first();
+
+            sender.try_send(0).unwrap();
+            sender.try_send(1).unwrap();
+            assert_eq!(future::poll_once(stream.next()).await, None);
+
+            // The first interval ends, which opens the next one.
+            clock.advance(period);
+            assert_eq!(future::poll_once(stream.next()).await, None);
+            sender.try_send(2).unwrap();
+            sender.try_send(3).unwrap();
+            assert_eq!(future::poll_once(stream.next()).await, Some(Some(2)));
+            assert_eq!(future::poll_once(stream.next()).await, None);
+        })
+    }
 }
@@ -1,12 +1,16 @@
-use std::io;
-use std::pin::Pin;
+use core::pin::Pin;
 
 use pin_project_lite::pin_project;
 
 use core::task::{Context, Poll};
-use futures_core::stream::Stream;
+use futures_core::stream::{FusedStream, Stream};
 
-use crate::{future::Timer, utils};
+#[cfg(feature = "std")]
+use crate::future::Deadline;
+use crate::future::{TimeoutError, Timer};
+#[cfg(feature = "std")]
+use crate::time::Instant;
+use crate::utils::Stopwatch;
 
 pin_project! {
     /// A stream with timeout time set
@@ -23,32 +27,120 @@ pin_project! {
         stream: S,
         #[pin]
         deadline: D,
+        stopwatch: Stopwatch,
+        started: bool,
     }
 }
 
 impl<S, D> Timeout<S, D> {
     pub(crate) fn new(stream: S, deadline: D) -> Self {
-        Self { stream, deadline }
+        Self {
+            stream,
+            deadline,
+            stopwatch: Stopwatch::start(),
+            started: false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, D: Deadline> Timeout<S, D> {
+    /// Returns the instant at which the next item times out.
+    ///
+    /// The deadline moves forward after every item and every timeout.
+    pub fn deadline(&self) -> Instant {
+        self.deadline.deadline()
     }
 }
 
 impl<S: Stream, D: Timer> Stream for Timeout<S, D> {
-    type Item = io::Result<S::Item>;
+    type Item = Result<S::Item, TimeoutError>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
+        // Time spent before the first poll does not count against the
+        // deadline of the first item.
+        if !*this.started {
+            *this.started = true;
+            this.deadline.as_mut().reset_timer();
+            this.stopwatch.restart();
+        }
+
         let r = match this.stream.poll_next(cx) {
             Poll::Ready(Some(v)) => Poll::Ready(Some(Ok(v))),
             Poll::Ready(None) => Poll::Ready(None),
             Poll::Pending => match this.deadline.as_mut().poll(cx) {
-                Poll::Ready(_) => Poll::Ready(Some(Err(utils::timeout_err("stream timed out")))),
+                Poll::Ready(_) => {
+                    trace!(elapsed = ?this.stopwatch.elapsed(), "stream timed out");
+                    let err = TimeoutError::new(
+                        this.stopwatch.try_elapsed(),
+                        this.deadline.timer_duration(),
+                    );
+                    Poll::Ready(Some(Err(err)))
+                }
                 Poll::Pending => return Poll::Pending,
             },
         };
 
         this.deadline.as_mut().reset_timer();
+        this.stopwatch.restart();
 
         r
     }
 }
+
+impl<S: FusedStream, D: Timer> FusedStream for Timeout<S, D> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::future;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn yields_late_items() {
+        crate::utils::block_on(async {
+            let mut stream = stream::once("meow")
+                .delay(Duration::from_millis(60))
+                .timeout(Duration::from_millis(40));
+            assert!(stream.next().await.unwrap().is_err());
+            assert_eq!(stream.next().await.unwrap().unwrap(), "meow");
+            assert!(stream.next().await.is_none());
+        })
+    }
+
+    #[test]
+    fn error_reports_durations() {
+        crate::utils::block_on(async {
+            let budget = Duration::from_millis(20);
+            let mut stream = stream::pending::<()>().timeout(budget);
+            let err = stream.next().await.unwrap().unwrap_err();
+            assert_eq!(err.configured(), Some(budget));
+            assert!(err.elapsed().unwrap() >= budget);
+        })
+    }
+
+    #[test]
+    fn starts_on_first_poll() {
+        crate::utils::block_on(async {
+            let mut stream = Box::pin(
+                stream::once_future(async {
+                    crate::task::sleep(Duration::from_millis(50)).await;
+                    "meow"
+                })
+                .timeout(Duration::from_millis(100)),
+            );
+
+            crate::task::sleep(Duration::from_millis(200)).await;
+            assert!(future::poll_once(stream.next()).await.is_none());
+            assert_eq!(stream.next().await.unwrap().unwrap(), "meow");
+        })
+    }
+}
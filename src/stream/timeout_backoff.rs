@@ -0,0 +1,128 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::{Deadline, TimeoutError};
+use crate::task::{sleep, Sleep};
+use crate::time::Duration;
+use crate::utils::Stopwatch;
+
+pin_project! {
+    /// A stream whose timeout grows while it stays idle.
+    ///
+    /// This `struct` is created by the [`timeout_backoff`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_backoff`]: crate::stream::StreamExt::timeout_backoff
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct TimeoutBackoff<S> {
+        #[pin]
+        stream: S,
+        deadline: Option<Sleep>,
+        base: Duration,
+        max: Duration,
+        factor: f64,
+        current: Duration,
+        stopwatch: Stopwatch,
+    }
+}
+
+impl<S> TimeoutBackoff<S> {
+    pub(crate) fn new(stream: S, base: Duration, max: Duration, factor: f64) -> Self {
+        assert!(factor >= 1.0, "backoff factor must be at least 1.0");
+        let base = base.min(max);
+        Self {
+            stream,
+            deadline: None,
+            base,
+            max,
+            factor,
+            current: base,
+            stopwatch: Stopwatch::start(),
+        }
+    }
+
+    /// Returns the duration the next item may currently take.
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+}
+
+impl<S: Stream> Stream for TimeoutBackoff<S> {
+    type Item = Result<S::Item, TimeoutError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        // The first deadline starts when the stream is first polled.
+        if this.deadline.is_none() {
+            *this.deadline = Some(sleep(*this.current));
+            this.stopwatch.restart();
+        }
+        let deadline = this.deadline.as_mut().unwrap();
+
+        match this.stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                *this.current = *this.base;
+                Pin::new(deadline).push_deadline(*this.current);
+                this.stopwatch.restart();
+                Poll::Ready(Some(Ok(item)))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => {
+                ready!(Pin::new(&mut *deadline).poll(cx));
+                trace!(elapsed = ?this.stopwatch.elapsed(), "stream timed out");
+                let err = TimeoutError::new(this.stopwatch.try_elapsed(), Some(*this.current));
+                let next = this.current.as_secs_f64() * *this.factor;
+                *this.current = match next < this.max.as_secs_f64() {
+                    true => Duration::from_secs_f64(next),
+                    false => *this.max,
+                };
+                Pin::new(deadline).push_deadline(*this.current);
+                this.stopwatch.restart();
+                Poll::Ready(Some(Err(err)))
+            }
+        }
+    }
+}
+
+impl<S: FusedStream> FusedStream for TimeoutBackoff<S> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn backs_off_and_resets() {
+        crate::utils::block_on(async {
+            let ms = Duration::from_millis;
+            let mut stream = stream::once(1)
+                .delay(ms(90))
+                .chain(stream::pending())
+                .timeout_backoff(ms(10), ms(40), 2.0);
+
+            let mut configured = vec![];
+            while let Err(err) = stream.next().await.unwrap() {
+                configured.push(err.configured().unwrap());
+            }
+            assert_eq!(configured, vec![ms(10), ms(20), ms(40)]);
+            assert_eq!(stream.current(), ms(10));
+
+            let err = stream.next().await.unwrap().unwrap_err();
+            assert_eq!(err.configured(), Some(ms(10)));
+        })
+    }
+}
@@ -0,0 +1,129 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::{Deadline, TimeoutError};
+use crate::task::{sleep, Sleep};
+use crate::time::Duration;
+use crate::utils::Stopwatch;
+
+pin_project! {
+    /// A stream with a timeout derived from each item.
+    ///
+    /// This `struct` is created by the [`timeout_dynamic`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_dynamic`]: crate::stream::StreamExt::timeout_dynamic
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct TimeoutDynamic<S, F> {
+        #[pin]
+        stream: S,
+        f: F,
+        deadline: Option<Sleep>,
+        dur: Duration,
+        stopwatch: Stopwatch,
+    }
+}
+
+impl<S, F> TimeoutDynamic<S, F> {
+    pub(crate) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            deadline: None,
+            dur: Duration::ZERO,
+            stopwatch: Stopwatch::start(),
+        }
+    }
+}
+
+impl<S, F> Stream for TimeoutDynamic<S, F>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> Duration,
+{
+    type Item = Result<S::Item, TimeoutError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        match this.stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                *this.dur = (this.f)(&item);
+                match this.deadline {
+                    Some(deadline) => Pin::new(deadline).push_deadline(*this.dur),
+                    None => *this.deadline = Some(sleep(*this.dur)),
+                }
+                this.stopwatch.restart();
+                Poll::Ready(Some(Ok(item)))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            // No deadline applies until the first item has been yielded.
+            Poll::Pending => match this.deadline {
+                Some(deadline) => {
+                    ready!(Pin::new(&mut *deadline).poll(cx));
+                    trace!(elapsed = ?this.stopwatch.elapsed(), "stream timed out");
+                    let err = TimeoutError::new(this.stopwatch.try_elapsed(), Some(*this.dur));
+                    Pin::new(deadline).push_deadline(*this.dur);
+                    this.stopwatch.restart();
+                    Poll::Ready(Some(Err(err)))
+                }
+                None => Poll::Pending,
+            },
+        }
+    }
+}
+
+impl<S, F> FusedStream for TimeoutDynamic<S, F>
+where
+    S: FusedStream,
+    F: FnMut(&S::Item) -> Duration,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::task::sleep;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn deadline_from_item() {
+        crate::utils::block_on(async {
+            // Each item carries the number of milliseconds until the next one
+            // is due, and the items themselves are 60ms apart.
+            let stream = stream::iter(vec![40, 200, 40]).then(|n| async move {
+                sleep(Duration::from_millis(60)).await;
+                n
+            });
+            let items: Vec<_> = stream
+                .timeout_dynamic(|n| Duration::from_millis(*n))
+                .map(|res| res.ok())
+                .collect()
+                .await;
+            assert_eq!(items, vec![Some(40), None, Some(200), Some(40)]);
+        })
+    }
+
+    #[test]
+    fn zero_requires_next_item_immediately() {
+        crate::utils::block_on(async {
+            let mut stream = stream::once(0)
+                .chain(stream::once(1).delay(Duration::from_millis(20)))
+                .timeout_dynamic(|_| Duration::ZERO);
+            assert_eq!(stream.next().await.unwrap().unwrap(), 0);
+            assert!(stream.next().await.unwrap().is_err());
+        })
+    }
+}
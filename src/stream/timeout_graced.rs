@@ -0,0 +1,190 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::{TimeoutError, Timer};
+use crate::utils::Stopwatch;
+
+pin_project! {
+    /// A stream with an idle timeout between items, and a grace period which
+    /// starts at the first item.
+    ///
+    /// This `struct` is created by the [`timeout_graced`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_graced`]: crate::stream::StreamExt::timeout_graced
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct TimeoutGraced<S, D, G> {
+        #[pin]
+        stream: S,
+        #[pin]
+        idle: D,
+        #[pin]
+        grace: G,
+        state: State,
+        idle_stopwatch: Stopwatch,
+        grace_stopwatch: Stopwatch,
+    }
+}
+
+/// Internal state.
+#[derive(Debug)]
+enum State {
+    /// The stream has not been polled yet.
+    Init,
+    /// No item has been received yet, so only the idle timer is armed.
+    Waiting,
+    /// An item has been received, so the grace timer is armed too.
+    Graced,
+    /// The grace period has passed and the error has been yielded, so the
+    /// closing `Ready(None)` is next.
+    TimedOut,
+    /// The closing `Ready(None)` has been yielded.
+    Done,
+}
+
+impl<S, D, G> TimeoutGraced<S, D, G> {
+    pub(crate) fn new(stream: S, idle: D, grace: G) -> Self {
+        Self {
+            stream,
+            idle,
+            grace,
+            state: State::Init,
+            idle_stopwatch: Stopwatch::start(),
+            grace_stopwatch: Stopwatch::start(),
+        }
+    }
+}
+
+impl<S: Stream, D: Timer, G: Timer> Stream for TimeoutGraced<S, D, G> {
+    type Item = Result<S::Item, TimeoutError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        match this.state {
+            // Time spent before the first poll does not count against the
+            // idle timer.
+            State::Init => {
+                *this.state = State::Waiting;
+                this.idle.as_mut().reset_timer();
+                this.idle_stopwatch.restart();
+            }
+            State::Waiting => {}
+            // Once the grace period has passed, the stream ends.
+            State::Graced => {
+                if this.grace.as_mut().poll(cx).is_ready() {
+                    trace!(elapsed = ?this.grace_stopwatch.elapsed(), "stream grace period ended");
+                    *this.state = State::TimedOut;
+                    let err = TimeoutError::new(
+                        this.grace_stopwatch.try_elapsed(),
+                        this.grace.timer_duration(),
+                    );
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+            State::TimedOut | State::Done => {
+                *this.state = State::Done;
+                return Poll::Ready(None);
+            }
+        }
+
+        let r = match this.stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                // The first item starts the grace period.
+                if let State::Waiting = this.state {
+                    *this.state = State::Graced;
+                    this.grace.as_mut().reset_timer();
+                    this.grace_stopwatch.restart();
+                }
+                Poll::Ready(Some(Ok(item)))
+            }
+            Poll::Ready(None) => {
+                *this.state = State::Done;
+                return Poll::Ready(None);
+            }
+            Poll::Pending => match this.idle.as_mut().poll(cx) {
+                Poll::Ready(_) => {
+                    trace!(elapsed = ?this.idle_stopwatch.elapsed(), "stream timed out");
+                    let err = TimeoutError::new(
+                        this.idle_stopwatch.try_elapsed(),
+                        this.idle.timer_duration(),
+                    );
+                    Poll::Ready(Some(Err(err)))
+                }
+                Poll::Pending => return Poll::Pending,
+            },
+        };
+
+        this.idle.as_mut().reset_timer();
+        this.idle_stopwatch.restart();
+
+        r
+    }
+}
+
+impl<S: Stream, D: Timer, G: Timer> FusedStream for TimeoutGraced<S, D, G> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_core::stream::FusedStream;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn idle_timer_fires_between_items() {
+        crate::utils::block_on(async {
+            let idle = Duration::from_millis(40);
+            let mut stream = futures_lite::stream::once(1)
+                .chain(futures_lite::stream::once(2).delay(Duration::from_millis(60)))
+                .timeout_graced(idle, Duration::from_secs(10));
+
+            assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+            let err = stream.next().await.unwrap().unwrap_err();
+            assert_eq!(err.configured(), Some(idle));
+            assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+            assert!(stream.next().await.is_none());
+            assert!(stream.is_terminated());
+        })
+    }
+
+    #[test]
+    fn grace_period_ends_the_stream() {
+        crate::utils::block_on(async {
+            let grace = Duration::from_millis(50);
+            let items: Vec<_> = crate::stream::interval(Duration::from_millis(20))
+                .timeout_graced(Duration::from_millis(100), grace)
+                .collect()
+                .await;
+
+            assert!(items.len() >= 2);
+            let (last, rest) = items.split_last().unwrap();
+            assert!(rest.iter().all(|item| item.is_ok()));
+            let err = last.as_ref().unwrap_err();
+            assert_eq!(err.configured(), Some(grace));
+            assert!(err.elapsed().unwrap() >= grace);
+        })
+    }
+
+    #[test]
+    fn grace_period_starts_at_first_item() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = futures_lite::stream::once(1)
+                .delay(Duration::from_millis(50))
+                .timeout_graced(Duration::from_millis(100), Duration::from_millis(20))
+                .collect()
+                .await;
+            assert_eq!(items.len(), 1);
+            assert_eq!(*items[0].as_ref().unwrap(), 1);
+        })
+    }
+}
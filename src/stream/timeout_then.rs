@@ -0,0 +1,136 @@
+use core::ops::ControlFlow;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::Timer;
+
+pin_project! {
+    /// A stream which decides what to do whenever its deadline passes.
+    ///
+    /// This `struct` is created by the [`timeout_then`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_then`]: crate::stream::StreamExt::timeout_then
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct TimeoutThen<S, D, F> {
+        #[pin]
+        stream: S,
+        #[pin]
+        deadline: D,
+        on_timeout: F,
+        started: bool,
+        done: bool,
+    }
+}
+
+impl<S, D, F> TimeoutThen<S, D, F> {
+    pub(crate) fn new(stream: S, deadline: D, on_timeout: F) -> Self {
+        Self {
+            stream,
+            deadline,
+            on_timeout,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl<S, D, F> Stream for TimeoutThen<S, D, F>
+where
+    S: Stream,
+    D: Timer,
+    F: FnMut() -> ControlFlow<(), Option<S::Item>>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        // Time spent before the first poll does not count against the
+        // deadline of the first item.
+        if !*this.started {
+            *this.started = true;
+            this.deadline.as_mut().reset_timer();
+        }
+
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.deadline.as_mut().reset_timer();
+                    return Poll::Ready(Some(item));
+                }
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => {}
+            }
+
+            if this.deadline.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            trace!("stream timed out");
+            this.deadline.as_mut().reset_timer();
+            match (this.on_timeout)() {
+                ControlFlow::Continue(Some(item)) => return Poll::Ready(Some(item)),
+                // Keep waiting, polling the new deadline to register a wakeup.
+                ControlFlow::Continue(None) => continue,
+                ControlFlow::Break(()) => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
+impl<S, D, F> FusedStream for TimeoutThen<S, D, F>
+where
+    S: Stream,
+    D: Timer,
+    F: FnMut() -> ControlFlow<(), Option<S::Item>>,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use core::ops::ControlFlow;
+
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn injects_then_stops() {
+        crate::utils::block_on(async {
+            let mut lapses = 0;
+            let items: Vec<_> = stream::once("meow")
+                .chain(stream::pending())
+                .timeout_then(Duration::from_millis(10), || {
+                    lapses += 1;
+                    match lapses {
+                        1 => ControlFlow::Continue(Some("heartbeat")),
+                        2 => ControlFlow::Continue(None),
+                        _ => ControlFlow::Break(()),
+                    }
+                })
+                .collect()
+                .await;
+
+            assert_eq!(items, vec!["meow", "heartbeat"]);
+            assert_eq!(lapses, 3);
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::TimeoutError;
+use crate::utils::Stopwatch;
+
+pin_project! {
+    /// A stream with a total time budget.
+    ///
+    /// This `struct` is created by the [`timeout_total`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_total`]: crate::stream::StreamExt::timeout_total
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct TimeoutTotal<S, D> {
+        #[pin]
+        stream: S,
+        #[pin]
+        deadline: D,
+        state: State,
+        stopwatch: Stopwatch,
+    }
+}
+
+/// Internal state.
+#[derive(Debug)]
+enum State {
+    /// Items are passed through until the deadline passes.
+    Streaming,
+    /// The deadline has passed and the error has been yielded, so the closing
+    /// `Ready(None)` is next.
+    TimedOut,
+    /// The closing `Ready(None)` has been yielded.
+    Done,
+}
+
+impl<S, D> TimeoutTotal<S, D> {
+    pub(crate) fn new(stream: S, deadline: D) -> Self {
+        Self {
+            stream,
+            deadline,
+            state: State::Streaming,
+            stopwatch: Stopwatch::start(),
+        }
+    }
+}
+
+impl<S: Stream, D: Future> Stream for TimeoutTotal<S, D> {
+    type Item = Result<S::Item, TimeoutError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        match this.state {
+            State::Streaming => {
+                if this.deadline.poll(cx).is_ready() {
+                    trace!(elapsed = ?this.stopwatch.elapsed(), "stream timed out");
+                    *this.state = State::TimedOut;
+                    let err = TimeoutError::new(this.stopwatch.try_elapsed(), None);
+                    return Poll::Ready(Some(Err(err)));
+                }
+                match this.stream.poll_next(cx) {
+                    Poll::Ready(Some(item)) => Poll::Ready(Some(Ok(item))),
+                    Poll::Ready(None) => {
+                        *this.state = State::Done;
+                        Poll::Ready(None)
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+            State::TimedOut => {
+                *this.state = State::Done;
+                Poll::Ready(None)
+            }
+            State::Done => panic!("stream polled after completion"),
+        }
+    }
+}
+
+impl<S: Stream, D: Future> FusedStream for TimeoutTotal<S, D> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn yields_one_error_then_ends() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = crate::stream::interval(Duration::from_millis(20))
+                .timeout_total(Duration::from_millis(50))
+                .collect()
+                .await;
+            assert_eq!(items.len(), 3);
+            assert!(items[..2].iter().all(|item| item.is_ok()));
+            assert!(items[2].is_err());
+        })
+    }
+
+    #[test]
+    fn ends_without_error_within_budget() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = futures_lite::stream::iter(0..3)
+                .timeout_total(Duration::from_secs(10))
+                .map(Result::unwrap)
+                .collect()
+                .await;
+            assert_eq!(items, vec![0, 1, 2]);
+        })
+    }
+}
@@ -0,0 +1,471 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Wake, Waker};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+use crate::future::{Deadline, TimeoutError};
+use crate::task::{sleep, Sleep};
+use crate::time::{Clock, Duration, Instant, SystemClock};
+
+/// The number of slots in the wheel. Deadlines further out than this many
+/// ticks share slots with nearer ones, and are skipped over until their turn.
+const SLOTS: u64 = 256;
+
+/// A timer shared between many streams, for timeouts at a coarse resolution.
+///
+/// Every [`StreamExt::timeout`] holds its own timer, and moves it forward
+/// after every item. With thousands of concurrent streams this becomes a
+/// significant amount of work for the reactor. Streams created with
+/// [`StreamExt::timeout_on`] instead share a single timer per wheel, which
+/// ticks once per `resolution` while any stream is waiting. Deadlines are
+/// rounded up to the next tick and hashed into a fixed number of buckets, so
+/// an item only moves a deadline in memory, and a stream registers with the
+/// wheel at most about once per timeout.
+///
+/// The shared timer is polled from the waker of the timer itself, so it has to
+/// be `Send`. This is why the wheel requires the `async-io` or `tokio` backend.
+/// The wheel reads the time from the same [`SystemClock`] as its timer, so it
+/// follows Tokio's paused and auto-advancing test time.
+///
+/// # Accuracy
+///
+/// A timeout never fires early, but it may fire up to one `resolution` late,
+/// plus the usual scheduling delay. A stream which received items since it
+/// registered is woken at its old deadline, and registers again for the new
+/// one. Pick a resolution which is small compared to the timeouts, but large
+/// enough that many deadlines share a tick.
+///
+/// [`StreamExt::timeout`]: crate::stream::StreamExt::timeout
+/// [`StreamExt::timeout_on`]: crate::stream::StreamExt::timeout_on
+/// [`SystemClock`]: crate::time::SystemClock
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::prelude::*;
+/// use futures_time::prelude::*;
+/// use futures_time::stream::TimeoutWheel;
+/// use futures_time::time::Duration;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let wheel = TimeoutWheel::new(Duration::from_millis(10));
+///         let mut stream = futures_lite::stream::pending::<()>()
+///             .timeout_on(&wheel, Duration::from_millis(50));
+///         assert!(stream.next().await.unwrap().is_err());
+///     });
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TimeoutWheel {
+    shared: Arc<Shared>,
+}
+
+/// The state shared between the handles of a wheel and the waker of its timer.
+struct Shared {
+    wheel: Mutex<Wheel>,
+    /// The timer is created on first use, so the wheel can be created outside
+    /// of a runtime. It is always locked after `wheel`.
+    timer: Mutex<Option<Pin<Box<Sleep>>>>,
+}
+
+impl TimeoutWheel {
+    /// Create a new wheel which ticks once per `resolution`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `resolution` is zero.
+    pub fn new(resolution: Duration) -> Self {
+        assert!(!resolution.is_zero(), "resolution must be non-zero");
+        let shared = Arc::new_cyclic(|weak| Shared {
+            wheel: Mutex::new(Wheel {
+                resolution,
+                origin: SystemClock.now(),
+                current: 0,
+                slots: (0..SLOTS).map(|_| Vec::new()).collect(),
+                len: 0,
+                armed: false,
+                waker: Waker::from(Arc::new(WheelWaker(weak.clone()))),
+            }),
+            timer: Mutex::new(None),
+        });
+        Self { shared }
+    }
+
+    /// Returns the resolution of the wheel.
+    pub fn resolution(&self) -> Duration {
+        self.shared.wheel.lock().unwrap().resolution
+    }
+
+    /// Make sure `waker` is woken once `deadline` has passed.
+    ///
+    /// `registration` holds the tick and waker of the previous call. If that
+    /// tick has not passed yet and the waker is the same, nothing is
+    /// registered, even if the deadline has moved since: the stream is woken
+    /// at the old tick and registers again then.
+    fn schedule(&self, deadline: Instant, registration: &mut Option<(u64, Waker)>, waker: &Waker) {
+        let mut woken = Vec::new();
+        {
+            let mut wheel = self.shared.wheel.lock().unwrap();
+            wheel.advance(SystemClock.now(), &mut woken);
+            let pending = matches!(
+                registration,
+                Some((tick, w)) if *tick > wheel.current && w.will_wake(waker)
+            );
+            if !pending {
+                if let Some((tick, waker)) = registration.take() {
+                    wheel.remove(tick, &waker);
+                }
+                let tick = wheel.tick_ceil(deadline).max(wheel.current + 1);
+                wheel.insert(tick, waker.clone());
+                *registration = Some((tick, waker.clone()));
+            }
+            self.shared.arm(&mut wheel, &mut woken);
+        }
+        woken.into_iter().for_each(Waker::wake);
+    }
+
+    /// Remove the registration made by `schedule`, if it has not fired yet.
+    fn deregister(&self, registration: &mut Option<(u64, Waker)>) {
+        // This runs on drop, so a poisoned wheel is skipped rather than
+        // panicking again.
+        if let (Some((tick, waker)), Ok(mut wheel)) =
+            (registration.take(), self.shared.wheel.lock())
+        {
+            wheel.remove(tick, &waker);
+        }
+    }
+}
+
+impl Shared {
+    /// Poll the timer for the next tick while anything is waiting, collecting
+    /// the wakers of ticks which have already started.
+    fn arm(&self, wheel: &mut Wheel, woken: &mut Vec<Waker>) {
+        let mut timer = self.timer.lock().unwrap();
+        let timer = timer.get_or_insert_with(|| Box::pin(sleep(wheel.resolution)));
+        while !wheel.armed && wheel.len > 0 {
+            let at = wheel.instant_of(wheel.current + 1);
+            timer.as_mut().set_deadline(at);
+            let mut cx = Context::from_waker(&wheel.waker);
+            match timer.as_mut().poll(&mut cx) {
+                Poll::Ready(_) => wheel.advance(at.max(SystemClock.now()), woken),
+                Poll::Pending => wheel.armed = true,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for TimeoutWheel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wheel = self.shared.wheel.lock().unwrap();
+        f.debug_struct("TimeoutWheel")
+            .field("resolution", &wheel.resolution)
+            .field("len", &wheel.len)
+            .finish()
+    }
+}
+
+/// The state of a wheel.
+struct Wheel {
+    resolution: Duration,
+    /// The instant of tick zero.
+    origin: Instant,
+    /// The last tick which has been processed.
+    current: u64,
+    /// Registered wakers, hashed by their tick.
+    slots: Vec<Vec<Entry>>,
+    /// The number of registered wakers.
+    len: usize,
+    /// Whether the timer has been polled for the next tick.
+    armed: bool,
+    /// The waker the timer is polled with.
+    waker: Waker,
+}
+
+/// A waker registered for a tick.
+struct Entry {
+    tick: u64,
+    waker: Waker,
+}
+
+impl Wheel {
+    /// The tick which has most recently started at `at`.
+    fn tick_floor(&self, at: Instant) -> u64 {
+        let nanos = at.duration_since(self.origin).as_nanos() / self.resolution.as_nanos();
+        nanos as u64
+    }
+
+    /// The first tick which starts at or after `at`.
+    fn tick_ceil(&self, at: Instant) -> u64 {
+        let elapsed = at.duration_since(self.origin).as_nanos();
+        let resolution = self.resolution.as_nanos();
+        elapsed.div_ceil(resolution) as u64
+    }
+
+    /// The instant at which `tick` starts.
+    fn instant_of(&self, tick: u64) -> Instant {
+        let nanos = self.resolution.as_nanos() * tick as u128;
+        let offset = std::time::Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        );
+        self.origin.saturating_add(offset.into())
+    }
+
+    fn insert(&mut self, tick: u64, waker: Waker) {
+        self.slots[(tick % SLOTS) as usize].push(Entry { tick, waker });
+        self.len += 1;
+    }
+
+    /// Remove the entry of `waker` for `tick`, if it is still registered.
+    fn remove(&mut self, tick: u64, waker: &Waker) {
+        let slot = &mut self.slots[(tick % SLOTS) as usize];
+        let found = slot
+            .iter()
+            .position(|entry| entry.tick == tick && entry.waker.will_wake(waker));
+        if let Some(i) = found {
+            slot.swap_remove(i);
+            self.len -= 1;
+        }
+    }
+
+    /// Process all ticks up to `now`, collecting the wakers which are due.
+    fn advance(&mut self, now: Instant, woken: &mut Vec<Waker>) {
+        let now_tick = self.tick_floor(now);
+        if now_tick <= self.current {
+            return;
+        }
+        // After a full turn every slot has been visited.
+        let turns = (now_tick - self.current).min(SLOTS);
+        for tick in self.current + 1..=self.current + turns {
+            let slot = &mut self.slots[(tick % SLOTS) as usize];
+            let mut i = 0;
+            while i < slot.len() {
+                if slot[i].tick <= now_tick {
+                    woken.push(slot.swap_remove(i).waker);
+                    self.len -= 1;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.current = now_tick;
+    }
+}
+
+/// Wakes the streams which are due when the timer of a wheel fires, and arms
+/// the timer for the next tick.
+struct WheelWaker(Weak<Shared>);
+
+impl Wake for WheelWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let shared = match self.0.upgrade() {
+            Some(shared) => shared,
+            None => return,
+        };
+        let mut woken = Vec::new();
+        {
+            let mut wheel = shared.wheel.lock().unwrap();
+            wheel.armed = false;
+            wheel.advance(SystemClock.now(), &mut woken);
+            shared.arm(&mut wheel, &mut woken);
+        }
+        woken.into_iter().for_each(Waker::wake);
+    }
+}
+
+pin_project! {
+    /// A stream with a timeout between items, driven by a shared [`TimeoutWheel`].
+    ///
+    /// This `struct` is created by the [`timeout_on`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`timeout_on`]: crate::stream::StreamExt::timeout_on
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct TimeoutOn<S> {
+        #[pin]
+        stream: S,
+        wheel: TimeoutWheel,
+        dur: Duration,
+        start: Option<Instant>,
+        registration: Option<(u64, Waker)>,
+    }
+
+    impl<S> PinnedDrop for TimeoutOn<S> {
+        fn drop(this: Pin<&mut Self>) {
+            // Don't leave a waker behind in the wheel.
+            let this = this.project();
+            this.wheel.deregister(this.registration);
+        }
+    }
+}
+
+impl<S> TimeoutOn<S> {
+    pub(crate) fn new(stream: S, wheel: &TimeoutWheel, dur: Duration) -> Self {
+        Self {
+            stream,
+            wheel: wheel.clone(),
+            dur,
+            start: None,
+            registration: None,
+        }
+    }
+}
+
+impl<S: Stream> Stream for TimeoutOn<S> {
+    type Item = Result<S::Item, TimeoutError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        // Time spent before the first poll does not count against the
+        // deadline of the first item.
+        let start = *this.start.get_or_insert_with(|| SystemClock.now());
+
+        match this.stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                *this.start = Some(SystemClock.now());
+                Poll::Ready(Some(Ok(item)))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => {
+                let now = SystemClock.now();
+                let deadline = start.saturating_add(*this.dur);
+                if now >= deadline {
+                    trace!(elapsed = ?now.duration_since(start), "stream timed out");
+                    *this.start = Some(now);
+                    let err = TimeoutError::new(Some(now.duration_since(start)), Some(*this.dur));
+                    return Poll::Ready(Some(Err(err)));
+                }
+                this.wheel.schedule(deadline, this.registration, cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<S: FusedStream> FusedStream for TimeoutOn<S> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimeoutWheel;
+    use crate::prelude::*;
+    use crate::time::{Duration, Instant};
+    use futures_lite::future;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn times_out_at_coarse_resolution() {
+        crate::utils::block_on(async {
+            let wheel = TimeoutWheel::new(Duration::from_millis(10));
+            let budget = Duration::from_millis(30);
+            let start = Instant::now();
+            let mut stream = futures_lite::stream::pending::<()>().timeout_on(&wheel, budget);
+
+            let err = stream.next().await.unwrap().unwrap_err();
+            assert_eq!(err.configured(), Some(budget));
+            assert!(err.elapsed().unwrap() >= budget);
+            assert!(start.elapsed() >= budget);
+            assert!(start.elapsed() < Duration::from_millis(500));
+        })
+    }
+
+    #[test]
+    fn items_move_the_deadline() {
+        crate::utils::block_on(async {
+            let wheel = TimeoutWheel::new(Duration::from_millis(5));
+            let items: Vec<_> = crate::stream::interval(Duration::from_millis(10))
+                .take(6)
+                .timeout_on(&wheel, Duration::from_millis(40))
+                .collect()
+                .await;
+            assert_eq!(items.len(), 6);
+            assert!(items.iter().all(|item| item.is_ok()));
+        })
+    }
+
+    #[test]
+    fn dropped_stream_deregisters() {
+        crate::utils::block_on(async {
+            let wheel = TimeoutWheel::new(Duration::from_millis(10));
+            let mut dropped =
+                futures_lite::stream::pending::<()>().timeout_on(&wheel, Duration::from_millis(20));
+            let mut kept =
+                futures_lite::stream::pending::<()>().timeout_on(&wheel, Duration::from_millis(50));
+
+            assert!(future::poll_once(dropped.next()).await.is_none());
+            assert!(future::poll_once(kept.next()).await.is_none());
+            assert!(format!("{:?}", wheel).contains("len: 2"));
+            drop(dropped);
+            assert!(format!("{:?}", wheel).contains("len: 1"));
+
+            // The timer keeps running for the stream which is left.
+            let start = Instant::now();
+            assert!(kept.next().await.unwrap().is_err());
+            assert!(start.elapsed() < Duration::from_millis(500));
+        })
+    }
+
+    #[test]
+    fn streams_share_a_wheel() {
+        crate::utils::block_on(async {
+            let wheel = TimeoutWheel::new(Duration::from_millis(10));
+            let mut short =
+                futures_lite::stream::pending::<()>().timeout_on(&wheel, Duration::from_millis(20));
+            let mut long =
+                futures_lite::stream::pending::<()>().timeout_on(&wheel, Duration::from_millis(60));
+
+            let start = Instant::now();
+            let (a, b) = future::zip(
+                async {
+                    short.next().await;
+                    start.elapsed()
+                },
+                async {
+                    long.next().await;
+                    start.elapsed()
+                },
+            )
+            .await;
+            assert!(a >= Duration::from_millis(20));
+            assert!(b >= Duration::from_millis(60));
+            assert!(a < b);
+        })
+    }
+
+    #[test]
+    #[cfg(not(feature = "async-io"))]
+    fn follows_paused_tokio_time() {
+        let rt = ::tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        let start = std::time::Instant::now();
+        rt.block_on(async {
+            let wheel = TimeoutWheel::new(Duration::from_millis(10));
+            let budget = Duration::from_secs(2);
+            let virtual_start = ::tokio::time::Instant::now();
+            let mut stream = futures_lite::stream::pending::<()>().timeout_on(&wheel, budget);
+
+            let err = stream.next().await.unwrap().unwrap_err();
+            assert!(err.elapsed().unwrap() >= budget);
+            assert!(virtual_start.elapsed() >= budget.into());
+        });
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+}
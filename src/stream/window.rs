@@ -0,0 +1,296 @@
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::fmt;
+use core::pin::Pin;
+
+use core::task::{Context, Poll, Waker};
+use futures_core::stream::{FusedStream, Stream};
+
+/// Split a stream into consecutive windows of time.
+///
+/// This `struct` is created by the [`window`] method on [`StreamExt`]. See its
+/// documentation for more.
+///
+/// [`window`]: crate::stream::StreamExt::window
+/// [`StreamExt`]: crate::stream::StreamExt
+#[must_use = "streams do nothing unless polled or .awaited"]
+pub struct Window<S: Stream, I> {
+    shared: Rc<RefCell<Shared<S, I>>>,
+}
+
+/// The items of a single window.
+///
+/// This `struct` is yielded by the [`Window`] stream. It yields the items of
+/// the underlying stream which arrive while its window is open, and ends when
+/// the window closes.
+#[must_use = "streams do nothing unless polled or .awaited"]
+pub struct WindowStream<S: Stream, I> {
+    shared: Rc<RefCell<Shared<S, I>>>,
+    slot: Rc<RefCell<Slot<S::Item>>>,
+}
+
+/// The state shared between the `Window` stream and the windows it yields.
+#[derive(Debug)]
+struct Shared<S: Stream, I> {
+    stream: Pin<Box<S>>,
+    interval: Pin<Box<I>>,
+    /// The window new items are added to.
+    current: Rc<RefCell<Slot<S::Item>>>,
+    /// Windows which have been opened, but not yet yielded.
+    opened: VecDeque<Rc<RefCell<Slot<S::Item>>>>,
+    /// The task waiting for the next window to open.
+    waker: Option<Waker>,
+    /// Whether the underlying stream has ended.
+    done: bool,
+}
+
+/// The items of a single window.
+#[derive(Debug)]
+struct Slot<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl<S, I> fmt::Debug for Window<S, I>
+where
+    S: Stream + fmt::Debug,
+    S::Item: fmt::Debug,
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Window")
+            .field("shared", &self.shared)
+            .finish()
+    }
+}
+
+impl<S, I> fmt::Debug for WindowStream<S, I>
+where
+    S: Stream + fmt::Debug,
+    S::Item: fmt::Debug,
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowStream")
+            .field("slot", &self.slot)
+            .finish()
+    }
+}
+
+impl<T> Slot<T> {
+    fn open() -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            items: VecDeque::new(),
+            closed: false,
+            waker: None,
+        }))
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<S: Stream, I: Stream> Shared<S, I> {
+    /// Move items from the underlying stream into the current window, and
+    /// open a new window if the interval has fired.
+    fn poll_source(&mut self, cx: &mut Context<'_>) {
+        if self.done {
+            return;
+        }
+
+        loop {
+            match self.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let mut slot = self.current.borrow_mut();
+                    slot.items.push_back(item);
+                    slot.wake();
+                }
+                Poll::Ready(None) => {
+                    self.done = true;
+                    self.close_current();
+                    self.wake();
+                    return;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if let Poll::Ready(Some(_)) = self.interval.as_mut().poll_next(cx) {
+            self.close_current();
+            self.current = Slot::open();
+            self.opened.push_back(self.current.clone());
+            self.wake();
+            // The interval needs to be polled again to schedule the next tick.
+            cx.waker().wake_by_ref();
+        }
+    }
+
+    fn close_current(&mut self) {
+        let mut slot = self.current.borrow_mut();
+        slot.closed = true;
+        slot.wake();
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<S: Stream, I> Window<S, I> {
+    pub(crate) fn new(stream: S, interval: I) -> Self {
+        let current = Slot::open();
+        let mut opened = VecDeque::new();
+        opened.push_back(current.clone());
+        let shared = Shared {
+            stream: Box::pin(stream),
+            interval: Box::pin(interval),
+            current,
+            opened,
+            waker: None,
+            done: false,
+        };
+        Self {
+            shared: Rc::new(RefCell::new(shared)),
+        }
+    }
+}
+
+impl<S: Stream, I: Stream> Window<S, I> {
+    fn next_window(&self) -> Option<WindowStream<S, I>> {
+        let slot = self.shared.borrow_mut().opened.pop_front()?;
+        Some(WindowStream {
+            shared: self.shared.clone(),
+            slot,
+        })
+    }
+}
+
+impl<S: Stream, I: Stream> Stream for Window<S, I> {
+    type Item = WindowStream<S, I>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(window) = self.next_window() {
+            return Poll::Ready(Some(window));
+        }
+
+        {
+            let mut shared = self.shared.borrow_mut();
+            if shared.done {
+                return Poll::Ready(None);
+            }
+            shared.waker = Some(cx.waker().clone());
+            shared.poll_source(cx);
+        }
+
+        match self.next_window() {
+            Some(window) => Poll::Ready(Some(window)),
+            None if self.shared.borrow().done => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<S: Stream, I: Stream> FusedStream for Window<S, I> {
+    fn is_terminated(&self) -> bool {
+        let shared = self.shared.borrow();
+        shared.done && shared.opened.is_empty()
+    }
+}
+
+impl<S: Stream, I: Stream> WindowStream<S, I> {
+    fn poll_slot(&self) -> Poll<Option<S::Item>> {
+        let mut slot = self.slot.borrow_mut();
+        match slot.items.pop_front() {
+            Some(item) => Poll::Ready(Some(item)),
+            None if slot.closed => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<S: Stream, I: Stream> Stream for WindowStream<S, I> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Poll::Ready(item) = self.poll_slot() {
+            return Poll::Ready(item);
+        }
+
+        // Drive the underlying stream ourselves, so the window makes progress
+        // even if the `Window` stream is not being polled.
+        self.slot.borrow_mut().waker = Some(cx.waker().clone());
+        self.shared.borrow_mut().poll_source(cx);
+        self.poll_slot()
+    }
+}
+
+impl<S: Stream, I: Stream> FusedStream for WindowStream<S, I> {
+    fn is_terminated(&self) -> bool {
+        let slot = self.slot.borrow();
+        slot.closed && slot.items.is_empty()
+    }
+}
+
+impl<S: Stream, I> Drop for WindowStream<S, I> {
+    fn drop(&mut self) {
+        // Whoever else is waiting may have relied on us to poll the underlying
+        // stream, so give them a chance to pick it up.
+        if let Ok(mut shared) = self.shared.try_borrow_mut() {
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::stream;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn items_arrive_in_windows() {
+        crate::utils::block_on(async {
+            let mut windows = stream::interval(Duration::from_millis(10))
+                .take(6)
+                .enumerate()
+                .map(|(i, _)| i)
+                .window(Duration::from_millis(35));
+
+            let mut seen = vec![];
+            let mut count = 0;
+            while let Some(window) = windows.next().await {
+                count += 1;
+                seen.extend(window.collect::<Vec<_>>().await);
+            }
+            assert_eq!(seen, vec![0, 1, 2, 3, 4, 5]);
+            assert!(count >= 2, "expected several windows, got {}", count);
+        })
+    }
+
+    #[test]
+    fn window_yields_items_as_they_arrive() {
+        crate::utils::block_on(async {
+            let mut windows = stream::interval(Duration::from_millis(5))
+                .take(3)
+                .window(Duration::from_secs(10));
+
+            let mut window = windows.next().await.unwrap();
+            assert!(window.next().await.is_some());
+            assert!(window.next().await.is_some());
+            assert!(window.next().await.is_some());
+            assert!(window.next().await.is_none());
+            assert!(windows.next().await.is_none());
+        })
+    }
+}
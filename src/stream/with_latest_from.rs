@@ -0,0 +1,129 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::stream::{FusedStream, Stream};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Pair each item with the latest item of another stream.
+    ///
+    /// This `struct` is created by the [`with_latest_from`] method on [`StreamExt`]. See its
+    /// documentation for more.
+    ///
+    /// [`with_latest_from`]: crate::stream::StreamExt::with_latest_from
+    /// [`StreamExt`]: crate::stream::StreamExt
+    #[derive(Debug)]
+    #[must_use = "streams do nothing unless polled or .awaited"]
+    pub struct WithLatestFrom<S, U: Stream> {
+        #[pin]
+        stream: S,
+        #[pin]
+        other: U,
+        latest: Option<U::Item>,
+        other_done: bool,
+        skip_without_latest: bool,
+        done: bool,
+    }
+}
+
+impl<S, U: Stream> WithLatestFrom<S, U> {
+    pub(crate) fn new(stream: S, other: U) -> Self {
+        Self {
+            stream,
+            other,
+            latest: None,
+            other_done: false,
+            skip_without_latest: false,
+            done: false,
+        }
+    }
+
+    /// Drop items which arrive before the other stream has yielded its first
+    /// item, rather than pairing them with `None`.
+    pub fn skip_without_latest(mut self) -> Self {
+        self.skip_without_latest = true;
+        self
+    }
+}
+
+impl<S, U> Stream for WithLatestFrom<S, U>
+where
+    S: Stream,
+    U: Stream,
+    U::Item: Clone,
+{
+    type Item = (S::Item, Option<U::Item>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        assert!(!*this.done, "stream polled after completion");
+
+        // Drain the other stream so the latest value is as fresh as possible.
+        while !*this.other_done {
+            match this.other.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => *this.latest = Some(item),
+                Poll::Ready(None) => *this.other_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if *this.skip_without_latest && this.latest.is_none() {
+                        continue;
+                    }
+                    return Poll::Ready(Some((item, this.latest.clone())));
+                }
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S, U> FusedStream for WithLatestFrom<S, U>
+where
+    S: Stream,
+    U: Stream,
+    U::Item: Clone,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::prelude::*;
+    use crate::time::Duration;
+    use futures_lite::prelude::*;
+    use futures_lite::stream;
+
+    #[test]
+    fn pairs_with_latest() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = stream::iter(0..2)
+                .with_latest_from(stream::iter(vec!["a", "b"]))
+                .collect()
+                .await;
+            assert_eq!(items, vec![(0, Some("b")), (1, Some("b"))]);
+        })
+    }
+
+    #[test]
+    fn skip_without_latest() {
+        crate::utils::block_on(async {
+            let items: Vec<_> = stream::iter(0..3)
+                .with_latest_from(stream::once("a").delay(Duration::from_millis(10)))
+                .skip_without_latest()
+                .collect()
+                .await;
+            assert!(items.is_empty());
+        })
+    }
+}
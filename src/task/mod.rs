@@ -1,7 +1,76 @@
 //! Types and Traits for working with asynchronous tasks.
+//!
+//! The deadline of a [`Sleep`] can be moved after it has been created. The
+//! [`Timer`] trait restarts it with the duration it was created with, and the
+//! [`Deadline`] trait moves it to an arbitrary point in time. Both traits are
+//! part of the [`prelude`], and since `Sleep` is `Unpin` its methods can be
+//! called through `Pin::new(&mut sleep)`.
+//!
+//! [`Timer`]: crate::future::Timer
+//! [`Deadline`]: crate::future::Deadline
+//! [`prelude`]: crate::prelude
+//!
+//! # Examples
+//!
+//! A watchdog which is rearmed on every heartbeat, and fires once the
+//! heartbeats stop:
+//!
+//! ```
+//! use futures_lite::future;
+//! use futures_lite::prelude::*;
+//! use futures_time::prelude::*;
+//! use futures_time::time::Duration;
+//! use futures_time::{stream, task};
+//! use std::pin::Pin;
+//!
+//! enum Event {
+//!     Heartbeat,
+//!     Expired,
+//! }
+//!
+//! fn main() {
+//!     async_io::block_on(async {
+//!         let mut heartbeats = stream::interval(Duration::from_millis(10))
+//!             .take(3)
+//!             .chain(futures_lite::stream::pending());
+//!         let mut watchdog = task::sleep(Duration::from_millis(50));
+//!
+//!         let mut beats = 0;
+//!         loop {
+//!             let heartbeat = async {
+//!                 heartbeats.next().await;
+//!                 Event::Heartbeat
+//!             };
+//!             let expired = async {
+//!                 (&mut watchdog).await;
+//!                 Event::Expired
+//!             };
+//!             match future::or(heartbeat, expired).await {
+//!                 Event::Heartbeat => {
+//!                     beats += 1;
+//!                     Pin::new(&mut watchdog).reset_timer();
+//!                 }
+//!                 Event::Expired => break,
+//!             }
+//!         }
+//!         assert_eq!(beats, 3);
+//!     });
+//! }
+//! ```
 
-mod sleep;
-mod sleep_until;
+mod timeout;
+mod yield_now;
 
-pub use sleep::{sleep, Sleep};
-pub use sleep_until::{sleep_until, SleepUntil};
+pub use timeout::timeout;
+pub use yield_now::{yield_now, YieldNow};
+
+cfg_rt! {
+    mod sleep;
+    mod sleep_until;
+
+    pub use sleep::{sleep, sleep_jittered, Sleep};
+    pub use sleep_until::{sleep_until, SleepUntil};
+
+    #[cfg(feature = "chrono")]
+    pub use sleep::sleep_until_datetime;
+}
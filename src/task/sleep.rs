@@ -2,55 +2,362 @@ use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use async_io::Timer as AsyncTimer;
+use futures_core::future::FusedFuture;
 use pin_project_lite::pin_project;
 
-use crate::future::Timer;
-use crate::time::{Duration, Instant};
+use crate::future::{Deadline, Timer};
+use crate::time::{Clock, Duration, Instant, SystemClock};
 
 /// Sleeps for the specified amount of time.
 ///
-/// This future can be `push_deadline` to be moved
+/// The deadline of the returned future can be moved using the [`Timer`] and
+/// [`Deadline`] traits.
 pub fn sleep(dur: Duration) -> Sleep {
-    Sleep {
-        dur,
-        timer: AsyncTimer::after(dur.into()),
-        completed: false,
-    }
+    Sleep::with_clock(dur, SystemClock)
+}
+
+/// Sleeps for `base` plus a random amount of time in `[0, jitter)`.
+///
+/// Jitter spreads out timers which would otherwise fire at the same time, such
+/// as retries of many clients after a shared outage. `random` is called once
+/// and should return a value uniformly distributed in `[0, 1)`; this lets the
+/// caller pick a source of randomness. Values outside of that range are
+/// clamped. The resulting duration can be read with [`Sleep::duration`].
+///
+/// # Example
+///
+/// ```
+/// use futures_time::task;
+/// use futures_time::time::Duration;
+///
+/// let base = Duration::from_millis(100);
+/// let jitter = Duration::from_millis(20);
+/// let sleep = task::sleep_jittered(base, jitter, || 0.5);
+/// assert_eq!(sleep.duration(), Duration::from_millis(110));
+/// ```
+pub fn sleep_jittered<R>(base: Duration, jitter: Duration, random: R) -> Sleep
+where
+    R: FnOnce() -> f64,
+{
+    sleep(crate::utils::jittered(base, jitter, random()))
+}
+
+/// Sleeps until the wall-clock time `datetime`.
+///
+/// The current wall-clock time is read once, and the returned future sleeps
+/// for the time remaining until `datetime` on the monotonic clock. If
+/// `datetime` is in the past the future completes right away. See
+/// [`time::duration_until`] for how this interacts with wall-clock
+/// adjustments.
+///
+/// [`time::duration_until`]: crate::time::duration_until
+///
+/// # Example
+///
+/// ```
+/// use chrono::Utc;
+/// use futures_time::task;
+/// use futures_time::time::Duration;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let at = Utc::now() + chrono::Duration::milliseconds(10);
+///         task::sleep_until_datetime(at).await;
+///         assert!(Utc::now() >= at);
+///     });
+/// }
+/// ```
+#[cfg(feature = "chrono")]
+pub fn sleep_until_datetime(datetime: ::chrono::DateTime<::chrono::Utc>) -> Sleep {
+    sleep(crate::time::duration_until(datetime, ::chrono::Utc::now()))
 }
 
 pin_project! {
     /// Sleeps for the specified amount of time.
+    ///
+    /// Once completed, polling it again resolves right away. This lets a
+    /// `&mut` reference to it be shared as the deadline of several timeouts.
+    #[derive(Debug)]
     #[must_use = "futures do nothing unless polled or .awaited"]
-    pub struct Sleep {
-        #[pin]
-        timer: AsyncTimer,
+    pub struct Sleep<C = SystemClock>
+    where
+        C: Clock,
+    {
+        clock: C,
+        timer: C::Timer,
         completed: bool,
+        deadline: Instant,
         dur: Duration,
+        lazy: bool,
+    }
+}
+
+impl<C: Clock> Sleep<C> {
+    /// Sleeps for the specified amount of time, using the given clock to
+    /// create the underlying timer.
+    pub fn with_clock(dur: Duration, clock: C) -> Self {
+        let deadline = clock.now().saturating_add(dur);
+        Self {
+            timer: clock.timer_at(deadline),
+            clock,
+            completed: false,
+            dur,
+            deadline,
+            lazy: false,
+        }
+    }
+
+    /// Start sleeping when the future is first polled, rather than when it
+    /// was created.
+    ///
+    /// Until then [`deadline`](Sleep::deadline) reports the instant the sleep
+    /// would complete at if it had started right away. Moving the deadline
+    /// through the [`Timer`] or [`Deadline`] traits before the first poll
+    /// takes precedence over this option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_time::task;
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// fn main() {
+    ///     async_io::block_on(async {
+    ///         let dur = Duration::from_millis(50);
+    ///         let sleep = task::sleep(dur).lazy_start();
+    ///         task::sleep(dur).await;
+    ///
+    ///         let start = Instant::now();
+    ///         sleep.await;
+    ///         assert!(start.elapsed() >= dur);
+    ///     });
+    /// }
+    /// ```
+    pub fn lazy_start(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// Returns the duration this sleep was created with.
+    ///
+    /// This is the duration the timer is moved forward by when it is reset
+    /// through [`Timer::reset_timer`].
+    pub fn duration(&self) -> Duration {
+        self.dur
+    }
+
+    /// Returns the instant at which this sleep completes.
+    ///
+    /// This reflects any changes made through the [`Timer`] and [`Deadline`]
+    /// traits.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
     }
 }
 
-impl Future for Sleep {
+impl<C: Clock> Future for Sleep<C> {
     type Output = Instant;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        assert!(!self.completed, "future polled after completing");
         let this = self.project();
-        match this.timer.poll(cx) {
+        // A sleep which has completed stays completed, so it can be shared
+        // as a deadline between several operations.
+        if *this.completed {
+            return Poll::Ready(*this.deadline);
+        }
+        if *this.lazy {
+            *this.lazy = false;
+            *this.deadline = this.clock.now().saturating_add(*this.dur);
+            this.clock.set_at(this.timer, *this.deadline);
+        }
+        match Pin::new(this.timer).poll(cx) {
             Poll::Ready(instant) => {
                 *this.completed = true;
-                Poll::Ready(instant.into())
+                Poll::Ready(instant)
             }
             Poll::Pending => Poll::Pending,
         }
     }
 }
 
-impl Timer for Sleep {
+impl<C: Clock> FusedFuture for Sleep<C> {
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+impl<C: Clock> Timer for Sleep<C> {
     /// Resets the timer to be `Instant::now()` + `Duration` into the future.
     fn reset_timer(self: std::pin::Pin<&mut Self>) {
-        let mut this = self.project();
-        this.timer.set_after(**this.dur);
+        let this = self.project();
+        *this.deadline = this.clock.now().saturating_add(*this.dur);
+        this.clock.set_at(this.timer, *this.deadline);
         *this.completed = false;
+        *this.lazy = false;
+    }
+
+    fn timer_duration(&self) -> Option<Duration> {
+        Some(self.dur)
+    }
+}
+
+impl<C: Clock> Deadline for Sleep<C> {
+    /// Moves the deadline to be `Instant::now()` + `dur` into the future.
+    fn push_deadline(self: Pin<&mut Self>, dur: Duration) {
+        let this = self.project();
+        *this.deadline = this.clock.now().saturating_add(dur);
+        this.clock.set_at(this.timer, *this.deadline);
+        *this.completed = false;
+        *this.lazy = false;
+    }
+
+    /// Moves the deadline to the given `Instant`.
+    fn set_deadline(self: Pin<&mut Self>, at: Instant) {
+        let this = self.project();
+        *this.deadline = at;
+        this.clock.set_at(this.timer, at);
+        *this.completed = false;
+        *this.lazy = false;
+    }
+
+    fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        match self.completed || self.deadline <= now {
+            true => None,
+            false => Some(self.deadline - now),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future;
+
+    #[test]
+    fn sleep_jittered() {
+        crate::utils::block_on(async {
+            let base = Duration::from_millis(100);
+            let jitter = Duration::from_millis(20);
+            assert_eq!(super::sleep_jittered(base, jitter, || 0.0).duration(), base);
+            assert_eq!(
+                super::sleep_jittered(base, jitter, || 0.25).duration(),
+                Duration::from_millis(105)
+            );
+            assert_eq!(
+                super::sleep_jittered(base, jitter, || 2.0).duration(),
+                Duration::from_millis(120)
+            );
+        })
+    }
+
+    #[test]
+    fn push_deadline() {
+        crate::utils::block_on(async {
+            let now = Instant::now();
+            let mut sleep = sleep(Duration::from_secs(3600));
+            Pin::new(&mut sleep).push_deadline(Duration::from_millis(10));
+            (&mut sleep).await;
+            assert!(now.elapsed() < Duration::from_secs(1));
+        })
+    }
+
+    #[test]
+    fn set_deadline() {
+        crate::utils::block_on(async {
+            let mut sleep = sleep(Duration::from_millis(10));
+            (&mut sleep).await;
+
+            let deadline = Instant::now() + Duration::from_millis(20);
+            Pin::new(&mut sleep).set_deadline(deadline);
+            assert!(future::poll_once(&mut sleep).await.is_none());
+            let fired = (&mut sleep).await;
+            assert!(fired >= deadline);
+        })
+    }
+
+    #[test]
+    fn remaining() {
+        let clock = crate::time::TestClock::new();
+        let mut sleep = Sleep::with_clock(Duration::from_secs(10), clock.clone());
+        assert_eq!(sleep.remaining(), Some(Duration::from_secs(10)));
+
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(sleep.remaining(), Some(Duration::from_secs(6)));
+
+        Pin::new(&mut sleep).reset_timer();
+        assert_eq!(sleep.remaining(), Some(Duration::from_secs(10)));
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(sleep.remaining(), None);
+    }
+
+    #[test]
+    fn zero_and_max_durations() {
+        crate::utils::block_on(async {
+            let mut sleep = super::sleep(Duration::ZERO);
+            assert!(future::poll_once(&mut sleep).await.is_some());
+
+            let mut sleep = super::sleep(Duration::MAX);
+            assert!(future::poll_once(&mut sleep).await.is_none());
+            assert!(sleep.remaining().unwrap() > Duration::from_days(365));
+            Pin::new(&mut sleep).reset_timer();
+            Pin::new(&mut sleep).push_deadline(Duration::MAX);
+
+            let mut interval = crate::stream::interval(Duration::MAX);
+            assert!(future::poll_once(interval.tick()).await.is_none());
+        })
+    }
+
+    #[test]
+    fn lazy_start() {
+        future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            let dur = Duration::from_millis(10);
+            let mut sleep = Sleep::with_clock(dur, clock.clone()).lazy_start();
+
+            // Time spent before the first poll is not counted.
+            clock.advance(Duration::from_millis(20));
+            assert!(future::poll_once(&mut sleep).await.is_none());
+            assert_eq!(sleep.deadline(), clock.now() + dur);
+            clock.advance(dur);
+            assert!(future::poll_once(&mut sleep).await.is_some());
+        })
+    }
+
+    #[test]
+    fn drop_many_sleeps() {
+        future::block_on(async {
+            let clock = crate::time::TestClock::new();
+            for _ in 0..100_000 {
+                let mut sleep = Sleep::with_clock(Duration::from_secs(3600), clock.clone());
+                assert!(future::poll_once(&mut sleep).await.is_none());
+                assert_eq!(clock.pending_timers(), 1);
+            }
+            // Dropping a pending sleep deregisters its timer.
+            assert_eq!(clock.pending_timers(), 0);
+        })
+    }
+
+    #[test]
+    fn deadline_follows_changes() {
+        let clock = crate::time::TestClock::new();
+        let start = clock.now();
+        let mut sleep = Sleep::with_clock(Duration::from_secs(10), clock.clone());
+        assert_eq!(sleep.deadline(), start + Duration::from_secs(10));
+
+        clock.advance(Duration::from_secs(1));
+        Pin::new(&mut sleep).reset_timer();
+        assert_eq!(sleep.deadline(), start + Duration::from_secs(11));
+
+        Pin::new(&mut sleep).push_deadline(Duration::from_secs(2));
+        assert_eq!(sleep.deadline(), start + Duration::from_secs(3));
+
+        Pin::new(&mut sleep).set_deadline(start);
+        assert_eq!(sleep.deadline(), start);
     }
 }
@@ -2,41 +2,159 @@ use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use async_io::Timer;
+use futures_core::future::FusedFuture;
 use pin_project_lite::pin_project;
 
-use crate::time::Instant;
+use crate::future::Deadline;
+use crate::time::{Clock, Duration, Instant, SystemClock};
 
 /// Sleeps until the specified instant.
-pub fn sleep_until(deadline: Instant) -> SleepUntil {
-    SleepUntil {
-        timer: Timer::at(deadline.into()),
-        completed: false,
-    }
+///
+/// The deadline can be either a [`time::Instant`](Instant) or a
+/// `std::time::Instant`. The deadline of the returned future can be moved
+/// using the [`Deadline`] trait.
+pub fn sleep_until<I>(deadline: I) -> SleepUntil
+where
+    I: Into<Instant>,
+{
+    SleepUntil::with_clock(deadline, SystemClock)
 }
 
 pin_project! {
     /// Sleeps until the specified instant.
+    ///
+    /// Once completed, polling it again resolves right away. This lets a
+    /// `&mut` reference to it be shared as the deadline of several timeouts.
+    #[derive(Debug)]
     #[must_use = "futures do nothing unless polled or .awaited"]
-    pub struct SleepUntil {
-        #[pin]
-        timer: Timer,
+    pub struct SleepUntil<C = SystemClock>
+    where
+        C: Clock,
+    {
+        clock: C,
+        timer: C::Timer,
         completed: bool,
+        deadline: Instant,
+    }
+}
+
+impl<C: Clock> SleepUntil<C> {
+    /// Sleeps until the specified instant, using the given clock to create the
+    /// underlying timer.
+    pub fn with_clock<I>(deadline: I, clock: C) -> Self
+    where
+        I: Into<Instant>,
+    {
+        let deadline = deadline.into();
+        Self {
+            timer: clock.timer_at(deadline),
+            clock,
+            completed: false,
+            deadline,
+        }
+    }
+
+    /// Returns the instant at which this sleep completes.
+    ///
+    /// This reflects any changes made through the [`Deadline`] trait.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
     }
 }
 
-impl Future for SleepUntil {
+impl<C: Clock> Future for SleepUntil<C> {
     type Output = Instant;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        assert!(!self.completed, "future polled after completing");
         let this = self.project();
-        match this.timer.poll(cx) {
+        // A sleep which has completed stays completed, so it can be shared
+        // as a deadline between several operations.
+        if *this.completed {
+            return Poll::Ready(*this.deadline);
+        }
+        match Pin::new(this.timer).poll(cx) {
             Poll::Ready(instant) => {
                 *this.completed = true;
-                Poll::Ready(instant.into())
+                Poll::Ready(instant)
             }
             Poll::Pending => Poll::Pending,
         }
     }
 }
+
+impl<C: Clock> FusedFuture for SleepUntil<C> {
+    fn is_terminated(&self) -> bool {
+        self.completed
+    }
+}
+
+impl<C: Clock> Deadline for SleepUntil<C> {
+    /// Moves the deadline to be `Instant::now()` + `dur` into the future.
+    fn push_deadline(self: Pin<&mut Self>, dur: Duration) {
+        let this = self.project();
+        *this.deadline = this.clock.now().saturating_add(dur);
+        this.clock.set_at(this.timer, *this.deadline);
+        *this.completed = false;
+    }
+
+    /// Moves the deadline to the given `Instant`.
+    ///
+    /// If `at` is in the past, the next poll completes right away.
+    fn set_deadline(self: Pin<&mut Self>, at: Instant) {
+        let this = self.project();
+        *this.deadline = at;
+        this.clock.set_at(this.timer, at);
+        *this.completed = false;
+    }
+
+    fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        match self.completed || self.deadline <= now {
+            true => None,
+            false => Some(self.deadline - now),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_lite::future;
+
+    #[test]
+    fn std_instant() {
+        crate::utils::block_on(async {
+            let deadline = std::time::Instant::now() + std::time::Duration::from_millis(10);
+            let sleep = sleep_until(deadline);
+            assert_eq!(sleep.deadline(), Instant::from(deadline));
+            sleep.await;
+            assert!(std::time::Instant::now() >= deadline);
+        })
+    }
+
+    #[test]
+    fn extend_deadline() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let mut sleep = sleep_until(start + Duration::from_millis(10));
+            let deadline = start + Duration::from_millis(50);
+            Pin::new(&mut sleep).set_deadline(deadline);
+            let fired = (&mut sleep).await;
+            assert!(fired >= deadline);
+        })
+    }
+
+    #[test]
+    fn deadline_in_the_past() {
+        crate::utils::block_on(async {
+            let start = Instant::now();
+            let mut sleep = sleep_until(start + Duration::from_secs(3600));
+            Pin::new(&mut sleep).set_deadline(start);
+            assert!(future::poll_once(&mut sleep).await.is_some());
+        })
+    }
+}
@@ -0,0 +1,49 @@
+use core::future::Future;
+
+use crate::future::{IntoFuture, Timeout};
+
+/// Return an error if a future does not complete within a given time span.
+///
+/// This is the free-function form of [`FutureExt::timeout`], and behaves the
+/// same way: the returned future resolves to `Ok` with the output of `future`
+/// if it completes first, and to `Err` with a
+/// [`TimeoutError`](crate::future::TimeoutError) if `deadline` completes first.
+///
+/// [`FutureExt::timeout`]: crate::future::FutureExt::timeout
+///
+/// # Example
+///
+/// ```
+/// use futures_time::task;
+/// use futures_time::time::Duration;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let res = task::timeout(Duration::from_millis(100), async { "meow" }).await;
+///         assert_eq!(res.unwrap(), "meow");
+///     });
+/// }
+/// ```
+pub fn timeout<F, D>(deadline: D, future: F) -> Timeout<F, D::IntoFuture>
+where
+    F: Future,
+    D: IntoFuture,
+{
+    let configured = deadline.configured_duration();
+    Timeout::new(future, deadline.into_future()).with_configured(configured)
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::task;
+    use crate::time::Duration;
+    use futures_lite::future;
+
+    #[test]
+    fn times_out() {
+        crate::utils::block_on(async {
+            let res = task::timeout(Duration::from_millis(10), future::pending::<()>()).await;
+            assert!(res.is_err());
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Yield control back to the executor once.
+///
+/// The returned future wakes itself and returns `Pending` the first time it
+/// is polled, and completes the second time. This gives the executor a chance
+/// to run other tasks, which keeps long-running loops inside async code from
+/// starving them.
+///
+/// # Example
+///
+/// ```
+/// use futures_time::task;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let mut sum = 0u64;
+///         for n in 0..10_000 {
+///             sum += n;
+///             if n % 1_000 == 0 {
+///                 task::yield_now().await;
+///             }
+///         }
+///         assert_eq!(sum, 49_995_000);
+///     });
+/// }
+/// ```
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// Yield control back to the executor once.
+///
+/// This `struct` is created by the [`yield_now`] function. See its
+/// documentation for more.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use crate::task;
+    use futures_lite::future;
+
+    #[test]
+    fn completes_after_one_repoll() {
+        crate::utils::block_on(async {
+            let mut fut = task::yield_now();
+            assert!(future::poll_once(&mut fut).await.is_none());
+            assert!(future::poll_once(&mut fut).await.is_some());
+        })
+    }
+}
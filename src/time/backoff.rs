@@ -0,0 +1,114 @@
+use super::Duration;
+
+/// Creates an endless schedule of exponentially growing durations.
+///
+/// The returned iterator yields `base`, `base * factor`, `base * factor^2`,
+/// and so on. Once a duration reaches `max`, `max` is yielded from then on. The
+/// schedule never ends, so it can be limited with [`Iterator::take`].
+///
+/// A `factor` of `1.0` yields `base` every time, and a `factor` between `0.0`
+/// and `1.0` makes the durations shrink towards zero. A negative or `NaN`
+/// `factor` is treated as `1.0`. A `base` larger than `max` starts at `max`.
+/// Durations saturate at `max` rather than overflowing, so this never panics.
+///
+/// The schedule can be passed to [`future::retry`] or [`stream::intervals`].
+/// To spread out clients which back off at the same time, add jitter to each
+/// duration with [`Iterator::map`].
+///
+/// [`future::retry`]: crate::future::retry
+/// [`stream::intervals`]: crate::stream::intervals
+///
+/// # Examples
+///
+/// ```
+/// use futures_time::time::{self, Duration};
+///
+/// let ms = Duration::from_millis;
+/// let schedule: Vec<_> = time::backoff(ms(10), 2.0, ms(50)).take(5).collect();
+/// assert_eq!(schedule, vec![ms(10), ms(20), ms(40), ms(50), ms(50)]);
+/// ```
+///
+/// Adding up to 10ms of jitter to each duration, using a caller-provided
+/// source of randomness:
+///
+/// ```
+/// use futures_time::time::{self, Duration};
+///
+/// let random = || 0.5; // a value in `[0, 1)`
+/// let ms = Duration::from_millis;
+/// let schedule = time::backoff(ms(10), 2.0, ms(50)).map(|dur| dur + ms(10).mul_f64(random()));
+/// assert_eq!(schedule.take(2).collect::<Vec<_>>(), vec![ms(15), ms(25)]);
+/// ```
+pub fn backoff(base: Duration, factor: f64, max: Duration) -> Backoff {
+    let factor = match factor >= 0.0 {
+        true => factor,
+        false => 1.0,
+    };
+    Backoff {
+        next: base.min(max),
+        factor,
+        max,
+    }
+}
+
+/// An endless schedule of exponentially growing durations.
+///
+/// This `struct` is created by the [`backoff`] function. See its
+/// documentation for more.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Backoff {
+    next: Duration,
+    factor: f64,
+    max: Duration,
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next;
+        let next = current.as_secs_f64() * self.factor;
+        self.next = match next < self.max.as_secs_f64() {
+            true => Duration::from_secs_f64(next),
+            false => self.max,
+        };
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl core::iter::FusedIterator for Backoff {}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::backoff;
+    use crate::time::Duration;
+
+    #[test]
+    fn saturates_at_max() {
+        let schedule: Vec<_> = backoff(Duration::from_secs(1), 1e300, Duration::MAX)
+            .take(3)
+            .collect();
+        assert_eq!(
+            schedule,
+            vec![Duration::from_secs(1), Duration::MAX, Duration::MAX]
+        );
+    }
+
+    #[test]
+    fn constant_and_decreasing() {
+        let ms = Duration::from_millis;
+        let constant: Vec<_> = backoff(ms(10), 1.0, ms(50)).take(3).collect();
+        assert_eq!(constant, vec![ms(10); 3]);
+        let nan: Vec<_> = backoff(ms(10), f64::NAN, ms(50)).take(3).collect();
+        assert_eq!(nan, vec![ms(10); 3]);
+        let decreasing: Vec<_> = backoff(ms(40), 0.5, ms(50)).take(3).collect();
+        assert_eq!(decreasing, vec![ms(40), ms(20), ms(10)]);
+        let capped: Vec<_> = backoff(ms(100), 2.0, ms(50)).take(2).collect();
+        assert_eq!(capped, vec![ms(50); 2]);
+    }
+}
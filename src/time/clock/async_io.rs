@@ -0,0 +1,47 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::Clock;
+use crate::time::{Duration, Instant};
+
+/// A clock backed by the `async-io` reactor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AsyncIoClock;
+
+/// A timer created by the [`AsyncIoClock`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct AsyncIoTimer(::async_io::Timer);
+
+impl Future for AsyncIoTimer {
+    type Output = Instant;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map(Instant::from)
+    }
+}
+
+impl Clock for AsyncIoClock {
+    type Timer = AsyncIoTimer;
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn timer_after(&self, dur: Duration) -> Self::Timer {
+        AsyncIoTimer(::async_io::Timer::after(dur.into()))
+    }
+
+    fn timer_at(&self, deadline: Instant) -> Self::Timer {
+        AsyncIoTimer(::async_io::Timer::at(deadline.into()))
+    }
+
+    fn set_after(&self, timer: &mut Self::Timer, dur: Duration) {
+        timer.0.set_after(dur.into());
+    }
+
+    fn set_at(&self, timer: &mut Self::Timer, deadline: Instant) {
+        timer.0.set_at(deadline.into());
+    }
+}
@@ -0,0 +1,154 @@
+use std::future::Future;
+
+use super::{Duration, Instant};
+
+#[cfg(feature = "async-io")]
+mod async_io;
+mod test_clock;
+#[cfg(feature = "tokio")]
+mod tokio;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod wasm;
+
+#[cfg(feature = "async-io")]
+pub use self::async_io::{AsyncIoClock, AsyncIoTimer};
+pub use self::test_clock::{TestClock, TestTimer};
+#[cfg(feature = "tokio")]
+pub use self::tokio::{TokioClock, TokioTimer};
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use self::wasm::{WasmClock, WasmTimer};
+
+// The default clock is picked from the enabled backends, in order of
+// precedence: `async-io`, `tokio`, `wasm`.
+#[cfg(feature = "async-io")]
+pub use self::async_io::{AsyncIoClock as SystemClock, AsyncIoTimer as SystemTimer};
+#[cfg(all(feature = "tokio", not(feature = "async-io")))]
+pub use self::tokio::{TokioClock as SystemClock, TokioTimer as SystemTimer};
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "wasm",
+    not(any(feature = "async-io", feature = "tokio"))
+))]
+pub use self::wasm::{WasmClock as SystemClock, WasmTimer as SystemTimer};
+
+/// A source of time which can create timers.
+///
+/// Every timer-backed type in this crate, such as [`task::Sleep`],
+/// [`task::SleepUntil`] and [`stream::Interval`], creates its timers through a
+/// `Clock`. By default they use [`SystemClock`], which is backed by the
+/// runtime selected through this crate's feature flags. Implementing this
+/// trait makes it possible to drive the crate's operators from any other
+/// source of time.
+///
+/// # Backends
+///
+/// The following clocks are provided, each behind a feature of the same name:
+///
+/// - `AsyncIoClock` is backed by the `async-io` reactor.
+/// - `TokioClock` is backed by `tokio::time`, and must be used from within a
+///   Tokio runtime with the time driver enabled.
+/// - `WasmClock` is backed by the browser's `setTimeout`, and is only
+///   available on `wasm32` targets.
+///
+/// The timers of each of these clocks are deregistered as soon as they are
+/// dropped. The types wrapping them hold no other timer state, so dropping a
+/// `Sleep`, `Interval` or `Timeout` before it fires releases its timer right
+/// away.
+///
+/// `SystemClock` refers to the first enabled backend in the order listed
+/// above. To use another backend than `async-io` as the default, disable this
+/// crate's default features.
+///
+/// For tests, [`TestClock`] provides a virtual clock which only moves forward
+/// when it is advanced, so timers can be driven deterministically.
+///
+/// [`task::Sleep`]: crate::task::Sleep
+/// [`task::SleepUntil`]: crate::task::SleepUntil
+/// [`stream::Interval`]: crate::stream::Interval
+pub trait Clock {
+    /// The timer created by this clock.
+    ///
+    /// A timer resolves with the instant at which it fired. Dropping a timer
+    /// must release whatever it registered to be woken up, so that timers
+    /// which are abandoned before they fire do not pile up.
+    type Timer: Future<Output = Instant> + Unpin;
+
+    /// Returns an instant corresponding to "now".
+    fn now(&self) -> Instant;
+
+    /// Creates a timer which fires after `dur` has elapsed.
+    fn timer_after(&self, dur: Duration) -> Self::Timer;
+
+    /// Creates a timer which fires at `deadline`.
+    fn timer_at(&self, deadline: Instant) -> Self::Timer;
+
+    /// Moves `timer` to fire after `dur` has elapsed. If the timer has already
+    /// fired, it will fire again.
+    fn set_after(&self, timer: &mut Self::Timer, dur: Duration);
+
+    /// Moves `timer` to fire at `deadline`. If the timer has already fired, it
+    /// will fire again.
+    fn set_at(&self, timer: &mut Self::Timer, deadline: Instant);
+}
+
+#[cfg(all(test, feature = "async-io"))]
+mod test {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::{Clock, SystemClock, SystemTimer};
+    use crate::stream::Interval;
+    use crate::task::Sleep;
+    use crate::time::{Duration, Instant};
+    use futures_lite::prelude::*;
+
+    /// A clock which counts how many timers it has armed.
+    #[derive(Debug, Default, Clone)]
+    struct CountingClock {
+        armed: Rc<Cell<usize>>,
+    }
+
+    impl Clock for CountingClock {
+        type Timer = SystemTimer;
+
+        fn now(&self) -> Instant {
+            SystemClock.now()
+        }
+
+        fn timer_after(&self, dur: Duration) -> Self::Timer {
+            self.armed.set(self.armed.get() + 1);
+            SystemClock.timer_after(dur)
+        }
+
+        fn timer_at(&self, deadline: Instant) -> Self::Timer {
+            self.armed.set(self.armed.get() + 1);
+            SystemClock.timer_at(deadline)
+        }
+
+        fn set_after(&self, timer: &mut Self::Timer, dur: Duration) {
+            self.armed.set(self.armed.get() + 1);
+            SystemClock.set_after(timer, dur)
+        }
+
+        fn set_at(&self, timer: &mut Self::Timer, deadline: Instant) {
+            self.armed.set(self.armed.get() + 1);
+            SystemClock.set_at(timer, deadline)
+        }
+    }
+
+    #[test]
+    fn custom_clock() {
+        crate::utils::block_on(async {
+            let clock = CountingClock::default();
+            let mut sleep = Sleep::with_clock(Duration::from_millis(5), clock.clone());
+            (&mut sleep).await;
+            assert_eq!(clock.armed.get(), 1);
+
+            let clock = CountingClock::default();
+            let mut interval = Interval::with_clock(Duration::from_millis(5), clock.clone());
+            interval.next().await;
+            interval.next().await;
+            assert_eq!(clock.armed.get(), 3);
+        })
+    }
+}
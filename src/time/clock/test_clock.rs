@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use super::Clock;
+use crate::time::{Duration, Instant};
+
+/// A virtual clock for tests, which only moves forward when told to.
+///
+/// Time stands still for a `TestClock` until [`TestClock::advance`] is called,
+/// at which point every timer whose deadline has been reached fires. This
+/// makes it possible to test time-based code deterministically and without
+/// waiting on the wall clock, by passing the clock to constructors such as
+/// [`Sleep::with_clock`] and [`Interval::with_clock`].
+///
+/// Clones of a `TestClock` share the same time.
+///
+/// [`Sleep::with_clock`]: crate::task::Sleep::with_clock
+/// [`Interval::with_clock`]: crate::stream::Interval::with_clock
+///
+/// # Example
+///
+/// ```
+/// use futures_lite::future::poll_once;
+/// use futures_time::task::Sleep;
+/// use futures_time::time::{Duration, TestClock};
+///
+/// fn main() {
+///     futures_lite::future::block_on(async {
+///         let clock = TestClock::new();
+///         let mut sleep = Sleep::with_clock(Duration::from_secs(60), clock.clone());
+///         assert!(poll_once(&mut sleep).await.is_none());
+///
+///         clock.advance(Duration::from_secs(60));
+///         assert!(poll_once(&mut sleep).await.is_some());
+///     });
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    now: Instant,
+    next_id: u64,
+    /// The deadlines and wakers of all timers which are waiting to fire.
+    waiting: BTreeMap<u64, (Instant, Waker)>,
+}
+
+impl TestClock {
+    /// Creates a new clock, starting at the current time.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                now: Instant::now(),
+                next_id: 0,
+                waiting: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Moves the clock forward by `dur`, firing all timers whose deadline has
+    /// been reached.
+    pub fn advance(&self, dur: Duration) {
+        let mut woken = vec![];
+        {
+            let mut state = self.state.lock().unwrap();
+            state.now += dur;
+            let now = state.now;
+            state
+                .waiting
+                .retain(|_, (deadline, waker)| match *deadline <= now {
+                    true => {
+                        woken.push(waker.clone());
+                        false
+                    }
+                    false => true,
+                });
+        }
+        // Wake the timers without holding the lock, so they can be polled
+        // from the wakers.
+        for waker in woken {
+            waker.wake();
+        }
+    }
+
+    /// Returns the number of timers which are waiting for the clock to reach
+    /// their deadline.
+    ///
+    /// A timer starts waiting when it is first polled, and stops waiting once
+    /// it fires or is dropped. This can be used to check that code does not
+    /// leave timers behind.
+    pub fn pending_timers(&self) -> usize {
+        self.state.lock().unwrap().waiting.len()
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A timer created by the [`TestClock`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct TestTimer {
+    state: Arc<Mutex<State>>,
+    id: u64,
+    deadline: Instant,
+}
+
+impl Future for TestTimer {
+    type Output = Instant;
+
+    /// Resolves to the deadline of the timer once the clock has reached it.
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        if state.now >= self.deadline {
+            state.waiting.remove(&self.id);
+            Poll::Ready(self.deadline)
+        } else {
+            let entry = (self.deadline, cx.waker().clone());
+            state.waiting.insert(self.id, entry);
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for TestTimer {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.waiting.remove(&self.id);
+        }
+    }
+}
+
+impl Clock for TestClock {
+    type Timer = TestTimer;
+
+    fn now(&self) -> Instant {
+        self.state.lock().unwrap().now
+    }
+
+    fn timer_after(&self, dur: Duration) -> Self::Timer {
+        self.timer_at(self.now().saturating_add(dur))
+    }
+
+    fn timer_at(&self, deadline: Instant) -> Self::Timer {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        TestTimer {
+            state: self.state.clone(),
+            id,
+            deadline,
+        }
+    }
+
+    fn set_after(&self, timer: &mut Self::Timer, dur: Duration) {
+        self.set_at(timer, self.now().saturating_add(dur))
+    }
+
+    fn set_at(&self, timer: &mut Self::Timer, deadline: Instant) {
+        timer.deadline = deadline;
+        let mut state = self.state.lock().unwrap();
+        let now = state.now;
+        if let Some(entry) = state.waiting.get_mut(&timer.id) {
+            entry.0 = deadline;
+            // A timer moved into the past fires right away.
+            if deadline <= now {
+                if let Some((_, waker)) = state.waiting.remove(&timer.id) {
+                    drop(state);
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use super::{Clock, TestClock};
+    use crate::channel;
+    use crate::prelude::*;
+    use crate::stream::Interval;
+    use crate::task::Sleep;
+    use crate::time::Duration;
+    use futures_lite::future::poll_once;
+    use futures_lite::prelude::*;
+
+    #[test]
+    fn debounce() {
+        crate::utils::block_on(async {
+            let clock = TestClock::new();
+            let window = Sleep::with_clock(Duration::from_millis(10), clock.clone());
+            let (sender, receiver) = channel::unbounded();
+            let mut stream = receiver.debounce(window);
+
+            sender.send(1).await.unwrap();
+            assert_eq!(poll_once(stream.next()).await, None);
+
+            // A new item within the window resets it.
+            clock.advance(Duration::from_millis(5));
+            sender.send(2).await.unwrap();
+            assert_eq!(poll_once(stream.next()).await, None);
+            clock.advance(Duration::from_millis(9));
+            assert_eq!(poll_once(stream.next()).await, None);
+
+            clock.advance(Duration::from_millis(1));
+            assert_eq!(poll_once(stream.next()).await, Some(Some(2)));
+        })
+    }
+
+    #[test]
+    fn interval() {
+        crate::utils::block_on(async {
+            let clock = TestClock::new();
+            let start = clock.now();
+            let mut interval = Interval::with_clock(Duration::from_secs(1), clock.clone());
+            assert_eq!(poll_once(interval.next()).await, None);
+
+            clock.advance(Duration::from_secs(3));
+            for n in 1..=3 {
+                let tick = interval.next().await.unwrap();
+                assert_eq!(tick - start, Duration::from_secs(n));
+            }
+            assert_eq!(poll_once(interval.next()).await, None);
+        })
+    }
+
+    #[test]
+    fn drop_releases_timers() {
+        crate::utils::block_on(async {
+            let clock = TestClock::new();
+            let hour = Duration::from_secs(3600);
+            for _ in 0..1_000 {
+                let mut sleep = Sleep::with_clock(hour, clock.clone());
+                assert!(poll_once(&mut sleep).await.is_none());
+                let mut interval = Interval::with_clock(hour, clock.clone());
+                assert!(poll_once(interval.next()).await.is_none());
+                let sleep = Sleep::with_clock(hour, clock.clone());
+                let mut timeout = futures_lite::future::pending::<()>().timeout(sleep);
+                assert!(poll_once(&mut timeout).await.is_none());
+                assert_eq!(clock.pending_timers(), 3);
+            }
+            assert_eq!(clock.pending_timers(), 0);
+        })
+    }
+}
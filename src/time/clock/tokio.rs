@@ -0,0 +1,146 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use super::Clock;
+use crate::time::{Duration, Instant};
+
+/// A clock backed by `tokio::time`.
+///
+/// Timers created by this clock must be polled from within a Tokio runtime
+/// which has the time driver enabled. Because it reads the time from Tokio,
+/// this clock respects Tokio's paused and auto-advancing test time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TokioClock;
+
+/// A timer created by the [`TokioClock`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct TokioTimer(Pin<Box<::tokio::time::Sleep>>);
+
+impl Future for TokioTimer {
+    type Output = Instant;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let sleep = self.0.as_mut();
+        let deadline = sleep.deadline();
+        // Tokio only fires a timer once its driver has turned, so a deadline
+        // which has already passed is checked here. This resolves it on the
+        // first poll, the same as the other backends.
+        if deadline <= ::tokio::time::Instant::now() {
+            return Poll::Ready(deadline.into_std().into());
+        }
+        sleep.poll(cx).map(|_| deadline.into_std().into())
+    }
+}
+
+impl Clock for TokioClock {
+    type Timer = TokioTimer;
+
+    fn now(&self) -> Instant {
+        ::tokio::time::Instant::now().into_std().into()
+    }
+
+    fn timer_after(&self, dur: Duration) -> Self::Timer {
+        TokioTimer(Box::pin(::tokio::time::sleep_until(deadline_after(dur))))
+    }
+
+    fn timer_at(&self, deadline: Instant) -> Self::Timer {
+        let deadline = ::tokio::time::Instant::from_std(deadline.into());
+        TokioTimer(Box::pin(::tokio::time::sleep_until(deadline)))
+    }
+
+    fn set_after(&self, timer: &mut Self::Timer, dur: Duration) {
+        timer.0.as_mut().reset(deadline_after(dur));
+    }
+
+    fn set_at(&self, timer: &mut Self::Timer, deadline: Instant) {
+        let deadline = ::tokio::time::Instant::from_std(deadline.into());
+        timer.0.as_mut().reset(deadline);
+    }
+}
+
+/// Compute `now + dur`, saturating to roughly 30 years from now on overflow,
+/// the same as `tokio::time::sleep` does.
+fn deadline_after(dur: Duration) -> ::tokio::time::Instant {
+    let now = ::tokio::time::Instant::now();
+    now.checked_add(dur.into())
+        .unwrap_or_else(|| now + std::time::Duration::from_secs(86400 * 365 * 30))
+}
+
+#[cfg(test)]
+mod test {
+    use super::TokioClock;
+    use crate::prelude::*;
+    use crate::stream::{Interval, RateLimit};
+    use crate::task::Sleep;
+    use crate::time::{Duration, Instant};
+    use futures_lite::prelude::*;
+    use futures_lite::{future, stream};
+
+    /// A runtime whose time only moves forward when every task is idle.
+    fn paused() -> ::tokio::runtime::Runtime {
+        ::tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn smoke() {
+        let rt = ::tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let now = Instant::now();
+            Sleep::with_clock(Duration::from_millis(10), TokioClock).await;
+            assert!(now.elapsed() >= Duration::from_millis(10));
+
+            let mut counter = 0;
+            Interval::with_clock(Duration::from_millis(5), TokioClock)
+                .take(3)
+                .for_each(|_| counter += 1)
+                .await;
+            assert_eq!(counter, 3);
+        })
+    }
+
+    #[test]
+    fn timeout() {
+        paused().block_on(async {
+            let dur = Duration::from_secs(10);
+            let start = ::tokio::time::Instant::now();
+            let res = future::pending::<()>()
+                .timeout(Sleep::with_clock(dur, TokioClock))
+                .await;
+            assert!(res.is_err());
+            assert!(start.elapsed() >= dur.into());
+
+            let res = future::ready(1)
+                .timeout(Sleep::with_clock(dur, TokioClock))
+                .await;
+            assert_eq!(res.unwrap(), 1);
+        })
+    }
+
+    #[test]
+    fn stream_adapters() {
+        paused().block_on(async {
+            let period = Duration::from_secs(1);
+            let start = ::tokio::time::Instant::now();
+            let items: Vec<_> = stream::iter(0..3)
+                .throttle(Interval::with_clock(period, TokioClock))
+                .collect()
+                .await;
+            assert_eq!(items, vec![0]);
+
+            let items: Vec<_> = RateLimit::with_clock(stream::iter(0..3), period, 1, TokioClock)
+                .collect()
+                .await;
+            assert_eq!(items, vec![0, 1, 2]);
+            assert!(start.elapsed() >= (period * 2).into());
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use std::convert::TryFrom;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use gloo_timers::future::TimeoutFuture;
+
+use super::Clock;
+use crate::time::{Duration, Instant};
+
+/// A clock backed by the browser's `setTimeout`.
+///
+/// Timers created by this clock have millisecond granularity, and must be
+/// polled from a `wasm-bindgen-futures` executor. The time is read from
+/// `performance.now()`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WasmClock;
+
+/// A timer created by the [`WasmClock`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled or .awaited"]
+pub struct WasmTimer {
+    deadline: Instant,
+    timeout: TimeoutFuture,
+}
+
+impl WasmTimer {
+    fn at(deadline: Instant) -> Self {
+        Self {
+            timeout: timeout_until(deadline),
+            deadline,
+        }
+    }
+}
+
+impl Future for WasmTimer {
+    type Output = Instant;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            futures_core::ready!(Pin::new(&mut self.timeout).poll(cx));
+            // JS timers are capped to `u32::MAX` milliseconds, and may fire
+            // early by a fraction of a millisecond. So rearm until the deadline
+            // has really passed.
+            if Instant::now() >= self.deadline {
+                return Poll::Ready(self.deadline);
+            }
+            self.timeout = timeout_until(self.deadline);
+        }
+    }
+}
+
+impl Clock for WasmClock {
+    type Timer = WasmTimer;
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn timer_after(&self, dur: Duration) -> Self::Timer {
+        WasmTimer::at(deadline_after(dur))
+    }
+
+    fn timer_at(&self, deadline: Instant) -> Self::Timer {
+        WasmTimer::at(deadline)
+    }
+
+    fn set_after(&self, timer: &mut Self::Timer, dur: Duration) {
+        *timer = WasmTimer::at(deadline_after(dur));
+    }
+
+    fn set_at(&self, timer: &mut Self::Timer, deadline: Instant) {
+        *timer = WasmTimer::at(deadline);
+    }
+}
+
+/// Compute `now + dur`, saturating to roughly 30 years from now on overflow.
+fn deadline_after(dur: Duration) -> Instant {
+    Instant::now().saturating_add(dur)
+}
+
+/// Create a JS timeout which fires at the deadline, rounded up to the next
+/// millisecond and capped at `u32::MAX` milliseconds.
+fn timeout_until(deadline: Instant) -> TimeoutFuture {
+    let remaining = deadline.duration_since(Instant::now());
+    let millis = remaining.as_micros().div_ceil(1_000);
+    TimeoutFuture::new(u32::try_from(millis).unwrap_or(u32::MAX))
+}
@@ -0,0 +1,34 @@
+use ::chrono::{DateTime, Utc};
+
+use super::Duration;
+
+/// Returns the time from `now` until the wall-clock time `target`.
+///
+/// If `target` is not after `now`, this returns [`Duration::ZERO`].
+///
+/// # Wall-clock time
+///
+/// `DateTime` is read from the system's wall clock, which can jump when it is
+/// adjusted, for example by NTP or by hand. The timers in this crate are based
+/// on a monotonic clock which is not affected by such jumps. The duration is
+/// computed once, so if the wall clock is adjusted afterwards a timer built
+/// from it will fire at the original moment rather than at the adjusted
+/// wall-clock time.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use futures_time::time::{self, Duration};
+///
+/// let now = Utc.with_ymd_and_hms(2024, 1, 1, 13, 0, 0).unwrap();
+/// let target = Utc.with_ymd_and_hms(2024, 1, 1, 14, 0, 0).unwrap();
+/// assert_eq!(time::duration_until(target, now), Duration::from_hours(1));
+/// assert_eq!(time::duration_until(now, target), Duration::ZERO);
+/// ```
+pub fn duration_until(target: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    (target - now)
+        .to_std()
+        .map(Duration::from)
+        .unwrap_or(Duration::ZERO)
+}
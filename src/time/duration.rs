@@ -1,12 +1,18 @@
-use crate::{
-    future::IntoFuture,
-    stream::{Interval, IntoStream},
-    task::Sleep,
-};
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::str::FromStr;
 
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+cfg_rt! {
+    use crate::{
+        future::IntoFuture,
+        stream::{Interval, IntoStream},
+        task::Sleep,
+    };
 
-use super::Instant;
+    use super::Instant;
+}
 
 /// A Duration type to represent a span of time, typically used for system
 /// timeouts.
@@ -14,36 +20,152 @@ use super::Instant;
 /// This type wraps `std::time::Duration` so we can implement traits on it
 /// without coherence issues, just like if we were implementing this in the
 /// stdlib.
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Hash, Clone, Copy)]
-pub struct Duration(pub(crate) std::time::Duration);
+///
+/// In particular `std::time::Duration` cannot implement [`IntoFuture`] or
+/// [`IntoStream`] itself: those traits are implemented for every future and
+/// stream, and the standard library would be free to make `Duration` one. A
+/// `std::time::Duration` converts into this type with `.into()`:
+///
+/// ```
+/// use futures_lite::future;
+/// use futures_time::prelude::*;
+/// use futures_time::time::Duration;
+///
+/// fn main() {
+///     async_io::block_on(async {
+///         let dur = std::time::Duration::from_millis(10);
+///         let res = future::pending::<()>().timeout(Duration::from(dur)).await;
+///         assert!(res.is_err());
+///     });
+/// }
+/// ```
+///
+/// [`IntoFuture`]: crate::future::IntoFuture
+/// [`IntoStream`]: crate::stream::IntoStream
+///
+/// The default value is [`Duration::ZERO`]. Arithmetic through the operators
+/// panics on overflow and underflow, like it does for `std::time::Duration`.
+/// Use the `checked_` and `saturating_` methods to handle those cases instead.
+#[derive(PartialEq, PartialOrd, Ord, Eq, Hash, Clone, Copy, Default)]
+pub struct Duration(pub(crate) core::time::Duration);
 impl Duration {
+    /// A duration of zero time.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// let dur = Duration::ZERO;
+    /// assert!(dur.is_zero());
+    /// assert_eq!(dur, Duration::from_secs(0));
+    /// ```
+    pub const ZERO: Duration = Duration(core::time::Duration::ZERO);
+
+    /// The maximum duration.
+    pub const MAX: Duration = Duration(core::time::Duration::MAX);
+
     /// Creates a new `Duration` from the specified number of whole seconds and
     /// additional nanoseconds.
     #[must_use]
     #[inline]
-    pub fn new(secs: u64, nanos: u32) -> Duration {
-        std::time::Duration::new(secs, nanos).into()
+    pub const fn new(secs: u64, nanos: u32) -> Duration {
+        Duration(core::time::Duration::new(secs, nanos))
+    }
+
+    /// Creates a new `Duration` from the specified number of whole days.
+    ///
+    /// # Panics
+    /// This constructor will panic if the number of days overflows `Duration`.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::from_days(1), Duration::from_hours(24));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_days(days: u64) -> Duration {
+        match days.checked_mul(24 * 60 * 60) {
+            Some(secs) => Duration::from_secs(secs),
+            None => panic!("overflow in Duration::from_days"),
+        }
+    }
+
+    /// Creates a new `Duration` from the specified number of whole hours.
+    ///
+    /// # Panics
+    /// This constructor will panic if the number of hours overflows `Duration`.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::from_hours(2), Duration::from_mins(120));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_hours(hours: u64) -> Duration {
+        match hours.checked_mul(60 * 60) {
+            Some(secs) => Duration::from_secs(secs),
+            None => panic!("overflow in Duration::from_hours"),
+        }
+    }
+
+    /// Creates a new `Duration` from the specified number of whole minutes.
+    ///
+    /// # Panics
+    /// This constructor will panic if the number of minutes overflows `Duration`.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::from_mins(5), Duration::from_secs(300));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_mins(mins: u64) -> Duration {
+        match mins.checked_mul(60) {
+            Some(secs) => Duration::from_secs(secs),
+            None => panic!("overflow in Duration::from_mins"),
+        }
     }
 
     /// Creates a new `Duration` from the specified number of whole seconds.
     #[must_use]
     #[inline]
-    pub fn from_secs(secs: u64) -> Duration {
-        std::time::Duration::from_secs(secs).into()
+    pub const fn from_secs(secs: u64) -> Duration {
+        Duration(core::time::Duration::from_secs(secs))
     }
 
     /// Creates a new `Duration` from the specified number of milliseconds.
     #[must_use]
     #[inline]
-    pub fn from_millis(millis: u64) -> Self {
-        std::time::Duration::from_millis(millis).into()
+    pub const fn from_millis(millis: u64) -> Self {
+        Duration(core::time::Duration::from_millis(millis))
     }
 
     /// Creates a new `Duration` from the specified number of microseconds.
     #[must_use]
     #[inline]
-    pub fn from_micros(micros: u64) -> Self {
-        std::time::Duration::from_micros(micros).into()
+    pub const fn from_micros(micros: u64) -> Self {
+        Duration(core::time::Duration::from_micros(micros))
+    }
+
+    /// Returns true if this `Duration` spans no time.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert!(Duration::ZERO.is_zero());
+    /// assert!(!Duration::from_millis(1).is_zero());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn is_zero(&self) -> bool {
+        self.0.is_zero()
     }
 
     /// Creates a new `Duration` from the specified number of seconds represented
@@ -62,7 +184,7 @@ impl Duration {
     #[must_use]
     #[inline]
     pub fn from_secs_f64(secs: f64) -> Duration {
-        std::time::Duration::from_secs_f64(secs).into()
+        core::time::Duration::from_secs_f64(secs).into()
     }
 
     /// Creates a new `Duration` from the specified number of seconds represented
@@ -73,39 +195,164 @@ impl Duration {
     #[must_use]
     #[inline]
     pub fn from_secs_f32(secs: f32) -> Duration {
-        std::time::Duration::from_secs_f32(secs).into()
+        core::time::Duration::from_secs_f32(secs).into()
+    }
+
+    /// Multiplies `Duration` by `f64`.
+    ///
+    /// # Panics
+    /// This method will panic if result is negative, overflows `Duration` or not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// let dur = Duration::new(2, 700_000_000);
+    /// assert_eq!(dur.mul_f64(3.14), Duration::new(8, 478_000_000));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn mul_f64(self, rhs: f64) -> Duration {
+        self.0.mul_f64(rhs).into()
+    }
+
+    /// Divides `Duration` by `f64`.
+    ///
+    /// # Panics
+    /// This method will panic if result is negative, overflows `Duration` or not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// let dur = Duration::new(2, 700_000_000);
+    /// assert_eq!(dur.div_f64(3.14), Duration::new(0, 859_872_611));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn div_f64(self, rhs: f64) -> Duration {
+        self.0.div_f64(rhs).into()
+    }
+
+    /// Checked `Duration` addition. Returns `None` if overflow occurred.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::from_secs(1).checked_add(Duration::from_secs(1)), Some(Duration::from_secs(2)));
+    /// assert_eq!(Duration::MAX.checked_add(Duration::from_secs(1)), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn checked_add(self, rhs: Duration) -> Option<Duration> {
+        match self.0.checked_add(rhs.0) {
+            Some(dur) => Some(Duration(dur)),
+            None => None,
+        }
+    }
+
+    /// Checked `Duration` subtraction. Returns `None` if the result would be
+    /// negative.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::from_secs(2).checked_sub(Duration::from_secs(1)), Some(Duration::from_secs(1)));
+    /// assert_eq!(Duration::ZERO.checked_sub(Duration::from_secs(1)), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn checked_sub(self, rhs: Duration) -> Option<Duration> {
+        match self.0.checked_sub(rhs.0) {
+            Some(dur) => Some(Duration(dur)),
+            None => None,
+        }
+    }
+
+    /// Checked `Duration` multiplication. Returns `None` if overflow occurred.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::from_secs(1).checked_mul(3), Some(Duration::from_secs(3)));
+    /// assert_eq!(Duration::MAX.checked_mul(2), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn checked_mul(self, rhs: u32) -> Option<Duration> {
+        match self.0.checked_mul(rhs) {
+            Some(dur) => Some(Duration(dur)),
+            None => None,
+        }
+    }
+
+    /// Saturating `Duration` addition. Returns [`Duration::MAX`] if overflow
+    /// occurred.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::MAX.saturating_add(Duration::from_secs(1)), Duration::MAX);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn saturating_add(self, rhs: Duration) -> Duration {
+        Duration(self.0.saturating_add(rhs.0))
+    }
+
+    /// Saturating `Duration` subtraction. Returns [`Duration::ZERO`] if the
+    /// result would be negative.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures_time::time::Duration;
+    ///
+    /// assert_eq!(Duration::ZERO.saturating_sub(Duration::from_secs(1)), Duration::ZERO);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn saturating_sub(self, rhs: Duration) -> Duration {
+        Duration(self.0.saturating_sub(rhs.0))
     }
 }
 
-impl std::ops::Deref for Duration {
-    type Target = std::time::Duration;
+impl core::ops::Deref for Duration {
+    type Target = core::time::Duration;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl std::ops::DerefMut for Duration {
+impl core::ops::DerefMut for Duration {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
-impl From<std::time::Duration> for Duration {
-    fn from(inner: std::time::Duration) -> Self {
+impl From<core::time::Duration> for Duration {
+    fn from(inner: core::time::Duration) -> Self {
         Self(inner)
     }
 }
 
-impl Into<std::time::Duration> for Duration {
-    fn into(self) -> std::time::Duration {
-        self.0
+impl From<Duration> for core::time::Duration {
+    fn from(dur: Duration) -> Self {
+        dur.0
     }
 }
 
 impl Add<Duration> for Duration {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// This panics if the result overflows. Use [`Duration::checked_add`] or
+    /// [`Duration::saturating_add`] to avoid this.
     fn add(self, rhs: Duration) -> Self::Output {
         (self.0 + rhs.0).into()
     }
@@ -120,6 +367,10 @@ impl AddAssign<Duration> for Duration {
 impl Sub<Duration> for Duration {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// This panics if `rhs` is larger than `self`. Use
+    /// [`Duration::checked_sub`] or [`Duration::saturating_sub`] to avoid this.
     fn sub(self, rhs: Duration) -> Self::Output {
         (self.0 - rhs.0).into()
     }
@@ -131,22 +382,432 @@ impl SubAssign<Duration> for Duration {
     }
 }
 
-impl IntoFuture for Duration {
-    type Output = Instant;
+impl Mul<u32> for Duration {
+    type Output = Self;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        (self.0 * rhs).into()
+    }
+}
+
+impl Mul<Duration> for u32 {
+    type Output = Duration;
+
+    fn mul(self, rhs: Duration) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl MulAssign<u32> for Duration {
+    fn mul_assign(&mut self, rhs: u32) {
+        *self = (self.0 * rhs).into()
+    }
+}
+
+impl Div<u32> for Duration {
+    type Output = Self;
+
+    fn div(self, rhs: u32) -> Self::Output {
+        (self.0 / rhs).into()
+    }
+}
+
+impl DivAssign<u32> for Duration {
+    fn div_assign(&mut self, rhs: u32) {
+        *self = (self.0 / rhs).into()
+    }
+}
+
+impl Sum<Duration> for Duration {
+    fn sum<I: Iterator<Item = Duration>>(iter: I) -> Self {
+        iter.map(|dur| dur.0).sum::<core::time::Duration>().into()
+    }
+}
+
+impl<'a> Sum<&'a Duration> for Duration {
+    fn sum<I: Iterator<Item = &'a Duration>>(iter: I) -> Self {
+        iter.map(|dur| dur.0).sum::<core::time::Duration>().into()
+    }
+}
+
+const NANOS_PER_MICRO: u128 = 1_000;
+const NANOS_PER_MILLI: u128 = 1_000_000;
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+const NANOS_PER_MIN: u128 = 60 * NANOS_PER_SEC;
+const NANOS_PER_HOUR: u128 = 60 * NANOS_PER_MIN;
+const NANOS_PER_DAY: u128 = 24 * NANOS_PER_HOUR;
+
+/// Units in descending order of magnitude, used for formatting.
+const UNITS: [(&str, u128); 7] = [
+    ("d", NANOS_PER_DAY),
+    ("h", NANOS_PER_HOUR),
+    ("m", NANOS_PER_MIN),
+    ("s", NANOS_PER_SEC),
+    ("ms", NANOS_PER_MILLI),
+    ("us", NANOS_PER_MICRO),
+    ("ns", 1),
+];
+
+/// Formats the duration as a compact, human-readable string such as `1m30s`
+/// or `2s500ms`.
+///
+/// The output can be parsed back into an equal `Duration` using
+/// [`FromStr`](#impl-FromStr-for-Duration).
+///
+/// # Examples
+///
+/// ```
+/// use futures_time::time::Duration;
+///
+/// assert_eq!(Duration::from_millis(1500).to_string(), "1s500ms");
+/// assert_eq!(Duration::from_secs(90).to_string(), "1m30s");
+/// assert_eq!(Duration::ZERO.to_string(), "0s");
+/// ```
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut nanos = self.0.as_nanos();
+        if nanos == 0 {
+            return f.write_str("0s");
+        }
+        for (unit, unit_nanos) in UNITS {
+            let count = nanos / unit_nanos;
+            if count > 0 {
+                write!(f, "{}{}", count, unit)?;
+                nanos %= unit_nanos;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats the duration the same way `std::time::Duration` does, such as
+/// `1.5s` or `200ms`, rather than as a wrapper around it.
+impl fmt::Debug for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+/// Parses a duration from a sequence of `<number><unit>` pairs, such as
+/// `500ms`, `2s` or `1m30s`.
+///
+/// Supported units are `d`, `h`, `m` (or `min`), `s`, `ms`, `us` (or `µs`) and
+/// `ns`. Numbers may contain a decimal fraction (`1.5s`), and pairs may be
+/// separated by whitespace (`2s 500ms`).
+///
+/// # Examples
+///
+/// ```
+/// use futures_time::time::Duration;
+///
+/// let dur: Duration = "1m30s".parse().unwrap();
+/// assert_eq!(dur, Duration::from_secs(90));
+///
+/// let dur: Duration = "1.5s".parse().unwrap();
+/// assert_eq!(dur, Duration::from_millis(1500));
+/// ```
+impl FromStr for Duration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Err(ParseDurationError::new(ParseErrorKind::Empty));
+        }
+
+        let mut total: u128 = 0;
+        while !rest.is_empty() {
+            let is_number = |c: char| c.is_ascii_digit() || c == '.';
+            let split = rest.find(|c| !is_number(c)).unwrap_or(rest.len());
+            let (number, tail) = rest.split_at(split);
+            let split = tail
+                .find(|c: char| is_number(c) || c.is_whitespace())
+                .unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(split);
 
-    type IntoFuture = Sleep;
+            let unit_nanos = match unit {
+                "d" => NANOS_PER_DAY,
+                "h" => NANOS_PER_HOUR,
+                "m" | "min" => NANOS_PER_MIN,
+                "s" => NANOS_PER_SEC,
+                "ms" => NANOS_PER_MILLI,
+                "us" | "µs" => NANOS_PER_MICRO,
+                "ns" => 1,
+                "" => return Err(ParseDurationError::new(ParseErrorKind::MissingUnit)),
+                _ => return Err(ParseDurationError::new(ParseErrorKind::UnknownUnit)),
+            };
+            let nanos = parse_number(number, unit_nanos)?;
+            total = total
+                .checked_add(nanos)
+                .ok_or_else(|| ParseDurationError::new(ParseErrorKind::Overflow))?;
+            rest = tail.trim_start();
+        }
 
-    fn into_future(self) -> Self::IntoFuture {
-        crate::task::sleep(self)
+        let secs = u64::try_from(total / NANOS_PER_SEC)
+            .map_err(|_| ParseDurationError::new(ParseErrorKind::Overflow))?;
+        Ok(Duration::new(secs, (total % NANOS_PER_SEC) as u32))
     }
 }
 
-impl IntoStream for Duration {
-    type Item = Instant;
+/// Parse a decimal number and scale it to nanoseconds.
+fn parse_number(number: &str, unit_nanos: u128) -> Result<u128, ParseDurationError> {
+    let invalid = || ParseDurationError::new(ParseErrorKind::InvalidNumber);
+    let overflow = || ParseDurationError::new(ParseErrorKind::Overflow);
 
-    type IntoStream = Interval;
+    let (int, frac) = match number.split_once('.') {
+        Some((int, frac)) => (int, frac),
+        None => (number, ""),
+    };
+    if int.is_empty() && frac.is_empty() {
+        return Err(invalid());
+    }
+
+    let int: u128 = match int {
+        "" => 0,
+        int => int.parse().map_err(|_| invalid())?,
+    };
+    let mut nanos = int.checked_mul(unit_nanos).ok_or_else(overflow)?;
+
+    // Scale the fractional digits down one at a time; digits beyond
+    // nanosecond precision are truncated.
+    let mut scale = unit_nanos;
+    for digit in frac.chars() {
+        let digit = digit.to_digit(10).ok_or_else(invalid)? as u128;
+        scale /= 10;
+        nanos += digit * scale;
+    }
+    Ok(nanos)
+}
+
+/// An error which can be returned when parsing a [`Duration`].
+///
+/// This error is used as the error type for the `FromStr` implementation of
+/// [`Duration`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDurationError {
+    kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseErrorKind {
+    Empty,
+    InvalidNumber,
+    MissingUnit,
+    UnknownUnit,
+    Overflow,
+}
+
+impl ParseDurationError {
+    fn new(kind: ParseErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for ParseDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self.kind {
+            ParseErrorKind::Empty => "cannot parse duration from empty string",
+            ParseErrorKind::InvalidNumber => "invalid number in duration",
+            ParseErrorKind::MissingUnit => "missing unit in duration",
+            ParseErrorKind::UnknownUnit => "unknown unit in duration",
+            ParseErrorKind::Overflow => "duration is too large",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDurationError {}
+
+cfg_rt! {
+    impl IntoFuture for Duration {
+        type Output = Instant;
+
+        type IntoFuture = Sleep;
+
+        fn into_future(self) -> Self::IntoFuture {
+            crate::task::sleep(self)
+        }
+
+        fn configured_duration(&self) -> Option<Duration> {
+            Some(*self)
+        }
+    }
+
+    impl IntoStream for Duration {
+        type Item = Instant;
+
+        type IntoStream = Interval;
+
+        fn into_stream(self) -> Self::IntoStream {
+            crate::stream::interval(self)
+        }
+    }
+}
+
+/// Serializes using the same representation as `std::time::Duration`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Duration {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Deserializes using the same representation as `std::time::Duration`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Duration {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        core::time::Duration::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::Duration;
+
+    #[test]
+    fn default_is_zero() {
+        assert_eq!(Duration::default(), Duration::ZERO);
+    }
+
+    #[test]
+    fn underflow() {
+        let one = Duration::from_secs(1);
+        let two = Duration::from_secs(2);
+        assert_eq!(one.checked_sub(two), None);
+        assert_eq!(one.saturating_sub(two), Duration::ZERO);
+        assert_eq!(two.checked_sub(one), Some(one));
+        assert_eq!(Duration::MAX.checked_add(one), None);
+        assert_eq!(Duration::MAX.saturating_add(one), Duration::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sub_underflow_panics() {
+        let _ = Duration::from_secs(1) - Duration::from_secs(2);
+    }
+
+    #[test]
+    fn scalar_arithmetic() {
+        let base = Duration::from_millis(100);
+        assert_eq!(base * 2, Duration::from_millis(200));
+        assert_eq!(2 * base, Duration::from_millis(200));
+        assert_eq!(base / 4, Duration::from_millis(25));
+        assert_eq!(base.mul_f64(1.5), Duration::from_millis(150));
+        assert_eq!(base.div_f64(0.5), Duration::from_millis(200));
+
+        let mut dur = base;
+        dur *= 3;
+        dur /= 2;
+        assert_eq!(dur, Duration::from_millis(150));
+    }
+
+    #[test]
+    fn sum() {
+        let stages = [Duration::from_millis(100), Duration::from_millis(250)];
+        assert_eq!(stages.iter().sum::<Duration>(), Duration::from_millis(350));
+        assert_eq!(
+            stages.iter().copied().sum::<Duration>(),
+            Duration::from_millis(350)
+        );
+        assert_eq!(
+            core::iter::empty::<Duration>().sum::<Duration>(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn checked_and_saturating() {
+        let one = Duration::from_secs(1);
+        assert_eq!(Duration::MAX.checked_add(one), None);
+        assert_eq!(Duration::ZERO.checked_sub(one), None);
+        assert_eq!(Duration::MAX.checked_mul(2), None);
+        assert_eq!(one.checked_mul(0), Some(Duration::ZERO));
+        assert_eq!(Duration::ZERO.saturating_sub(one), Duration::ZERO);
+        assert_eq!(Duration::MAX.saturating_add(one), Duration::MAX);
+    }
+
+    #[test]
+    fn constructors() {
+        const TIMEOUT: Duration = Duration::from_mins(5);
+        assert_eq!(TIMEOUT, Duration::from_secs(300));
+        assert_eq!(Duration::from_hours(1), Duration::from_secs(3600));
+        assert_eq!(Duration::from_days(1), Duration::from_secs(86400));
+        assert!(Duration::ZERO.is_zero());
+        assert_eq!(Duration::MAX, Duration::from(core::time::Duration::MAX));
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let cases = [
+            ("0s", Duration::ZERO),
+            ("500ms", Duration::from_millis(500)),
+            ("2s", Duration::from_secs(2)),
+            ("1m30s", Duration::from_secs(90)),
+            ("2s500ms", Duration::from_millis(2500)),
+            ("1d2h3m4s5ms6us7ns", Duration::new(93784, 5_006_007)),
+        ];
+        for (input, dur) in cases {
+            assert_eq!(input.parse::<Duration>().unwrap(), dur);
+            assert_eq!(dur.to_string(), input);
+        }
+
+        let dur = Duration::from_millis(1500);
+        assert_eq!(dur.to_string().parse::<Duration>().unwrap(), dur);
+        assert_eq!("1.5s".parse::<Duration>().unwrap(), dur);
+        assert_eq!(" 1s 500ms ".parse::<Duration>().unwrap(), dur);
+        assert_eq!("3min".parse::<Duration>().unwrap(), Duration::from_mins(3));
+        assert_eq!(
+            "10µs".parse::<Duration>().unwrap(),
+            Duration::from_micros(10)
+        );
+    }
+
+    #[test]
+    fn display_magnitudes() {
+        assert_eq!(Duration::new(0, 1).to_string(), "1ns");
+        assert_eq!(Duration::new(0, 999).to_string(), "999ns");
+        assert_eq!(Duration::from_micros(1).to_string(), "1us");
+        assert_eq!(Duration::new(0, 999_999).to_string(), "999us999ns");
+        assert_eq!(Duration::from_millis(1).to_string(), "1ms");
+        assert_eq!(Duration::from_millis(999).to_string(), "999ms");
+        assert_eq!(Duration::from_secs(1).to_string(), "1s");
+        assert_eq!(Duration::from_secs(59).to_string(), "59s");
+        assert_eq!(Duration::from_secs(60).to_string(), "1m");
+        assert_eq!(Duration::from_secs(200).to_string(), "3m20s");
+        assert_eq!(Duration::from_secs(3600).to_string(), "1h");
+        assert_eq!(Duration::from_secs(86400).to_string(), "1d");
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format!("{:?}", Duration::from_millis(200)), "200ms");
+        assert_eq!(format!("{:?}", Some(Duration::ZERO)), "Some(0ns)");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("".parse::<Duration>().is_err());
+        assert!("10".parse::<Duration>().is_err());
+        assert!("ms".parse::<Duration>().is_err());
+        assert!("10 parsecs".parse::<Duration>().is_err());
+        assert!("1.2.3s".parse::<Duration>().is_err());
+        assert!("999999999999999999999999d".parse::<Duration>().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_days_overflow() {
+        let _ = Duration::from_days(u64::MAX);
+    }
 
-    fn into_stream(self) -> Self::IntoStream {
-        crate::stream::interval(self)
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let dur = Duration::new(2, 700_000_000);
+        let json = serde_json::to_string(&dur).unwrap();
+        assert_eq!(json, r#"{"secs":2,"nanos":700000000}"#);
+        assert_eq!(serde_json::from_str::<Duration>(&json).unwrap(), dur);
     }
 }
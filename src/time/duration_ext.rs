@@ -0,0 +1,76 @@
+use super::Duration;
+
+/// Extend `u64` with methods to create a [`Duration`].
+///
+/// Because this is only implemented for `u64`, integer literals such as
+/// `100.millis()` are inferred to be a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use futures_time::prelude::*;
+/// use futures_time::time::Duration;
+///
+/// assert_eq!(100.millis(), Duration::from_millis(100));
+/// assert_eq!(5.secs(), Duration::from_secs(5));
+/// ```
+pub trait DurationExt {
+    /// Creates a `Duration` from this number of microseconds.
+    fn micros(self) -> Duration;
+
+    /// Creates a `Duration` from this number of milliseconds.
+    fn millis(self) -> Duration;
+
+    /// Creates a `Duration` from this number of seconds.
+    fn secs(self) -> Duration;
+
+    /// Creates a `Duration` from this number of minutes.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the result overflows `Duration`.
+    fn mins(self) -> Duration;
+
+    /// Creates a `Duration` from this number of hours.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the result overflows `Duration`.
+    fn hours(self) -> Duration;
+}
+
+impl DurationExt for u64 {
+    fn micros(self) -> Duration {
+        Duration::from_micros(self)
+    }
+
+    fn millis(self) -> Duration {
+        Duration::from_millis(self)
+    }
+
+    fn secs(self) -> Duration {
+        Duration::from_secs(self)
+    }
+
+    fn mins(self) -> Duration {
+        Duration::from_mins(self)
+    }
+
+    fn hours(self) -> Duration {
+        Duration::from_hours(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DurationExt;
+    use crate::time::Duration;
+
+    #[test]
+    fn literals() {
+        assert_eq!(10.micros(), Duration::from_micros(10));
+        assert_eq!(10.millis(), Duration::from_millis(10));
+        assert_eq!(2.mins(), Duration::from_secs(120));
+        assert_eq!(1.hours(), Duration::from_secs(3600));
+    }
+}
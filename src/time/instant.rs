@@ -1,17 +1,37 @@
-use crate::{future::IntoFuture, task::SleepUntil};
-
+use std::fmt;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 use super::Duration;
 
+// `std::time::Instant` panics in the browser, so on wasm we substitute a
+// drop-in replacement which reads the time from `performance.now()`.
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+use std::time::Instant as StdInstant;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+use web_time::Instant as StdInstant;
+
+cfg_rt! {
+    use crate::{future::IntoFuture, task::SleepUntil};
+}
+
 /// A measurement of a monotonically nondecreasing clock. Opaque and useful only
 /// with Duration.
 ///
 /// This type wraps `std::time::Duration` so we can implement traits on it
 /// without coherence issues, just like if we were implementing this in the
-/// stdlib.
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Hash, Clone, Copy)]
-pub struct Instant(pub(crate) std::time::Instant);
+/// stdlib. A `std::time::Instant` converts into this type with `.into()`
+/// before it can be used as a deadline.
+///
+/// # Serde
+///
+/// With the `serde` feature enabled `Instant` can be serialized and
+/// deserialized. Because an `Instant` has no absolute meaning outside of the
+/// process that created it, it is serialized as the [`Duration`] remaining
+/// until the instant is reached. Deserializing adds that duration to
+/// [`Instant::now`]. Instants which lie in the past are serialized as a
+/// zero-length duration, and will deserialize to "now".
+#[derive(PartialEq, PartialOrd, Ord, Eq, Hash, Clone, Copy)]
+pub struct Instant(pub(crate) StdInstant);
 
 impl Instant {
     /// Returns an instant corresponding to "now".
@@ -25,7 +45,90 @@ impl Instant {
     /// ```
     #[must_use]
     pub fn now() -> Self {
-        std::time::Instant::now().into()
+        StdInstant::now().into()
+    }
+
+    /// Returns the amount of time elapsed from another instant to this one,
+    /// or zero duration if that instant is later than this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// let earlier = Instant::now();
+    /// let later = earlier + Duration::from_secs(1);
+    /// assert_eq!(later.duration_since(earlier), Duration::from_secs(1));
+    /// assert_eq!(earlier.duration_since(later), Duration::from_secs(0));
+    /// ```
+    #[must_use]
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        self.0.saturating_duration_since(earlier.0).into()
+    }
+
+    /// Returns the amount of time elapsed from another instant to this one,
+    /// or `None` if that instant is later than this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// let earlier = Instant::now();
+    /// let later = earlier + Duration::from_secs(1);
+    /// assert_eq!(later.checked_duration_since(earlier), Some(Duration::from_secs(1)));
+    /// assert_eq!(earlier.checked_duration_since(later), None);
+    /// ```
+    #[must_use]
+    pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        self.0.checked_duration_since(earlier.0).map(Duration::from)
+    }
+
+    /// Returns the amount of time elapsed since this instant was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// let instant = Instant::now();
+    /// assert!(instant.elapsed() < Duration::from_secs(1));
+    /// ```
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(*self)
+    }
+
+    /// Returns `self + duration`, or an instant far in the future if that
+    /// would overflow.
+    ///
+    /// The far-future instant lies roughly 30 years after `self`, which is
+    /// how timers armed with a very long duration are treated as "never".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_time::time::{Duration, Instant};
+    ///
+    /// let now = Instant::now();
+    /// assert_eq!(now.saturating_add(Duration::from_secs(1)), now + Duration::from_secs(1));
+    /// assert!(now.saturating_add(Duration::MAX) > now + Duration::from_days(365));
+    /// ```
+    #[must_use]
+    pub fn saturating_add(self, duration: Duration) -> Instant {
+        match self.0.checked_add(duration.0) {
+            Some(instant) => instant.into(),
+            None => self + FAR_FUTURE,
+        }
+    }
+}
+
+/// How far ahead an overflowing deadline is moved to.
+const FAR_FUTURE: Duration = Duration::from_days(365 * 30);
+
+impl fmt::Debug for Instant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
     }
 }
 
@@ -57,8 +160,18 @@ impl SubAssign<Duration> for Instant {
     }
 }
 
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    /// Returns the amount of time elapsed from another instant to this one,
+    /// or zero duration if that instant is later than this one.
+    fn sub(self, rhs: Instant) -> Self::Output {
+        self.duration_since(rhs)
+    }
+}
+
 impl std::ops::Deref for Instant {
-    type Target = std::time::Instant;
+    type Target = StdInstant;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -71,24 +184,133 @@ impl std::ops::DerefMut for Instant {
     }
 }
 
-impl From<std::time::Instant> for Instant {
-    fn from(inner: std::time::Instant) -> Self {
+impl From<StdInstant> for Instant {
+    fn from(inner: StdInstant) -> Self {
         Self(inner)
     }
 }
 
-impl Into<std::time::Instant> for Instant {
-    fn into(self) -> std::time::Instant {
-        self.0
+impl From<Instant> for StdInstant {
+    fn from(instant: Instant) -> Self {
+        instant.0
+    }
+}
+
+cfg_rt! {
+    impl IntoFuture for Instant {
+        type Output = Instant;
+
+        type IntoFuture = SleepUntil;
+
+        fn into_future(self) -> Self::IntoFuture {
+            crate::task::sleep_until(self)
+        }
     }
 }
 
-impl IntoFuture for Instant {
-    type Output = Instant;
+#[cfg(feature = "serde")]
+impl serde::Serialize for Instant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let remaining = self.0.saturating_duration_since(StdInstant::now());
+        Duration::from(remaining).serialize(serializer)
+    }
+}
 
-    type IntoFuture = SleepUntil;
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Instant {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The input may not be trusted, so a duration which is too large to
+        // represent saturates rather than panics.
+        let remaining = Duration::deserialize(deserializer)?;
+        Ok(Instant::now().saturating_add(remaining))
+    }
+}
+
+#[cfg(all(test, any(feature = "async-io", feature = "tokio")))]
+mod test {
+    use super::{Duration, Instant};
+
+    #[test]
+    fn checked_duration_since() {
+        let now = Instant::now();
+        let later = now + Duration::from_millis(10);
+        assert_eq!(
+            later.checked_duration_since(now),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(now.checked_duration_since(later), None);
+        assert_eq!(now.checked_duration_since(now), Some(Duration::ZERO));
+        assert_eq!(now.duration_since(later), Duration::ZERO);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let now = Instant::now();
+        let deadline = now + Duration::from_secs(5);
+        assert_eq!(deadline - now, Duration::from_secs(5));
+        assert_eq!(now - deadline, Duration::from_secs(0));
+        assert_eq!(deadline - Duration::from_secs(5), now);
+
+        let mut instant = now;
+        instant += Duration::from_millis(10);
+        instant -= Duration::from_millis(5);
+        assert_eq!(instant.duration_since(now), Duration::from_millis(5));
+    }
+
+    #[test]
+    fn as_deadline() {
+        use crate::prelude::*;
+
+        crate::utils::block_on(async {
+            let deadline = Instant::now() + Duration::from_millis(10);
+            assert_eq!(deadline.into_future().await, deadline);
+
+            let deadline = Instant::now() + Duration::from_millis(10);
+            let value = async { "meow" }.delay(deadline).await;
+            assert_eq!(value, "meow");
+            assert!(Instant::now() >= deadline);
+
+            let deadline = Instant::now() + Duration::from_millis(10);
+            let res = futures_lite::future::pending::<()>()
+                .timeout(deadline)
+                .await;
+            assert!(res.is_err());
+
+            let deadline = Instant::now() + Duration::from_secs(10);
+            let res = async { "meow" }.timeout(deadline).await;
+            assert_eq!(res.unwrap(), "meow");
+        })
+    }
+
+    #[test]
+    fn debug() {
+        let now = Instant::now();
+        assert_eq!(format!("{:?}", now), format!("{:?}", now.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let json = serde_json::to_string(&deadline).unwrap();
+        let parsed: Instant = serde_json::from_str(&json).unwrap();
+        assert!(parsed >= deadline);
+        assert!(parsed - deadline < Duration::from_secs(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_past_instant() {
+        let past = Instant::now();
+        let json = serde_json::to_string(&past).unwrap();
+        assert_eq!(json, r#"{"secs":0,"nanos":0}"#);
+    }
 
-    fn into_future(self) -> Self::IntoFuture {
-        crate::task::sleep_until(self)
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_huge_duration() {
+        let json = serde_json::to_string(&Duration::MAX).unwrap();
+        let parsed: Instant = serde_json::from_str(&json).unwrap();
+        assert!(parsed > Instant::now());
     }
 }
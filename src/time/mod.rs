@@ -3,8 +3,33 @@
 //! This submodule wraps the types in `std::time` so we can implement traits on
 //! them. Each type can be converted to-and-from their respective counterparts.
 
+mod backoff;
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(feature = "chrono")]
+mod datetime;
 mod duration;
+mod duration_ext;
+#[cfg(feature = "std")]
 mod instant;
 
-pub use duration::Duration;
+pub use backoff::{backoff, Backoff};
+#[cfg(feature = "std")]
+pub use clock::{Clock, TestClock, TestTimer};
+#[cfg(feature = "chrono")]
+pub use datetime::duration_until;
+pub use duration::{Duration, ParseDurationError};
+pub use duration_ext::DurationExt;
+#[cfg(feature = "std")]
 pub use instant::Instant;
+
+#[cfg(feature = "async-io")]
+pub use clock::{AsyncIoClock, AsyncIoTimer};
+#[cfg(feature = "tokio")]
+pub use clock::{TokioClock, TokioTimer};
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use clock::{WasmClock, WasmTimer};
+
+cfg_rt! {
+    pub use clock::{SystemClock, SystemTimer};
+}
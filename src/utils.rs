@@ -1,5 +1,112 @@
-use std::io;
+/// Declares items which require a timer backend to be enabled.
+///
+/// This also accepts provided methods inside a trait definition.
+macro_rules! cfg_rt {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                feature = "async-io",
+                feature = "tokio",
+                all(target_arch = "wasm32", feature = "wasm")
+            ))]
+            $item
+        )*
+    };
+}
+
+/// Emits a `tracing` event at the `TRACE` level when the `tracing` feature is
+/// enabled, and compiles to nothing otherwise.
+#[cfg(feature = "tracing")]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        tracing::trace!(target: "futures_time", $($arg)*)
+    };
+}
+
+/// Emits a `tracing` event at the `TRACE` level when the `tracing` feature is
+/// enabled, and compiles to nothing otherwise.
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace {
+    ($($arg:tt)*) => {};
+}
+
+/// Measures the time elapsed since it was started, for use in `trace!` events
+/// and in [`TimeoutError::elapsed`](crate::future::TimeoutError::elapsed).
+///
+/// With the `std` feature this reads the clock every time it is (re)started.
+/// Without it there is no clock, so this is zero-sized and measures nothing.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Stopwatch {
+    #[cfg(feature = "std")]
+    started: crate::time::Instant,
+}
+
+impl Stopwatch {
+    /// Start a new stopwatch.
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            started: crate::time::Instant::now(),
+        }
+    }
+
+    /// Restart the stopwatch from the current time.
+    pub(crate) fn restart(&mut self) {
+        *self = Self::start();
+    }
+
+    /// The time elapsed since the stopwatch was last (re)started.
+    #[cfg(feature = "std")]
+    pub(crate) fn elapsed(&self) -> crate::time::Duration {
+        self.started.elapsed()
+    }
+
+    /// The time elapsed since the stopwatch was last (re)started, if it can be
+    /// measured. Without the `std` feature there is no clock to measure with.
+    pub(crate) fn try_elapsed(&self) -> Option<crate::time::Duration> {
+        #[cfg(feature = "std")]
+        return Some(self.elapsed());
+        #[cfg(not(feature = "std"))]
+        return None;
+    }
+}
+
+/// Adds a fraction `random` of `jitter` to `base`, saturating on overflow.
+///
+/// `random` is expected to be in `[0, 1)`; values outside of that range are
+/// clamped, and `NaN` is treated as `0`.
+#[allow(dead_code)]
+pub(crate) fn jittered(
+    base: crate::time::Duration,
+    jitter: crate::time::Duration,
+    random: f64,
+) -> crate::time::Duration {
+    let random = if random.is_nan() {
+        0.0
+    } else {
+        random.clamp(0.0, 1.0)
+    };
+    // `mul_f64` panics on overflow, which rounding can cause for huge jitters.
+    let offset = core::time::Duration::try_from_secs_f64(jitter.as_secs_f64() * random)
+        .map(crate::time::Duration::from)
+        .unwrap_or(jitter);
+    base.saturating_add(offset)
+}
+
+/// Runs a future to completion for a test, on the runtime of the timer
+/// backend the crate is built with.
+#[cfg(all(test, feature = "async-io"))]
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    async_io::block_on(future)
+}
 
-pub(crate) fn timeout_err(msg: &'static str) -> io::Error {
-    io::Error::new(io::ErrorKind::TimedOut, msg)
+/// Runs a future to completion for a test, on the runtime of the timer
+/// backend the crate is built with.
+#[cfg(all(test, feature = "tokio", not(feature = "async-io")))]
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    ::tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(future)
 }
@@ -0,0 +1,57 @@
+#![cfg(feature = "async-io")]
+
+use futures_lite::prelude::*;
+use futures_time::prelude::*;
+use futures_time::task::sleep;
+use futures_time::time::Duration;
+
+/// A stream whose state machine holds an `async` block, which makes it
+/// `!Unpin`.
+fn not_unpin_stream() -> impl Stream<Item = u8> {
+    futures_lite::stream::unfold(0, |n| async move {
+        sleep(Duration::from_millis(1)).await;
+        match n < 3 {
+            true => Some((n, n + 1)),
+            false => None,
+        }
+    })
+}
+
+#[test]
+fn not_unpin_futures() {
+    async_io::block_on(async {
+        let fut = async {
+            sleep(Duration::from_millis(1)).await;
+            "meow"
+        };
+        assert_eq!(fut.delay(Duration::from_millis(1)).await, "meow");
+
+        let fut = async {
+            sleep(Duration::from_millis(1)).await;
+            "meow"
+        };
+        let res = fut.timeout(Duration::from_secs(1)).await;
+        assert_eq!(res.unwrap(), "meow");
+    })
+}
+
+#[test]
+fn not_unpin_streams() {
+    async_io::block_on(async {
+        let window = Duration::from_millis(50);
+        let items: Vec<_> = not_unpin_stream().throttle(window).collect().await;
+        assert_eq!(items, vec![0]);
+
+        let items: Vec<_> = not_unpin_stream().debounce(window).collect().await;
+        assert_eq!(items, vec![2]);
+
+        let items: Vec<_> = not_unpin_stream().sample(window).collect().await;
+        assert_eq!(items, vec![2]);
+
+        let items: Vec<_> = not_unpin_stream()
+            .timeout(Duration::from_secs(1))
+            .collect()
+            .await;
+        assert_eq!(items.len(), 3);
+    })
+}
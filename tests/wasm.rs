@@ -0,0 +1,43 @@
+//! Browser tests for the `wasm` timer backend.
+//!
+//! Run with:
+//!
+//! ```sh
+//! $ cargo test --target wasm32-unknown-unknown --no-default-features --features wasm --test wasm
+//! ```
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use futures_lite::prelude::*;
+use futures_time::prelude::*;
+use futures_time::stream;
+use futures_time::time::{Duration, Instant};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn delay() {
+    let now = Instant::now();
+    let res = async { "meow" }.delay(Duration::from_millis(20)).await;
+    assert_eq!(res, "meow");
+    assert!(now.elapsed() >= Duration::from_millis(20));
+}
+
+#[wasm_bindgen_test]
+async fn timeout() {
+    let res = async { "meow" }
+        .delay(Duration::from_millis(100))
+        .timeout(Duration::from_millis(20))
+        .await;
+    assert!(res.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn interval() {
+    let mut counter = 0;
+    stream::interval(Duration::from_millis(10))
+        .take(3)
+        .for_each(|_| counter += 1)
+        .await;
+    assert_eq!(counter, 3);
+}